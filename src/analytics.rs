@@ -0,0 +1,116 @@
+//! Playtest stats for tuning difficulty during the jam.
+//!
+//! Nothing is recorded unless the game is built with the `analytics` feature *and* the player
+//! launches it with `--playtest-stats`. Even then, stats only go to a local JSON file
+//! ([`STATS_PATH`]) that the player can read and choose to send us. Only aggregate counts are
+//! kept: time played per level and how often each command was used, never what was typed.
+//!
+//! Levels don't have a win or lose condition yet, so there's nothing to record about outcomes.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{game::LevelAssets, screens::Screen, terminal::CommandsRan};
+
+/// Where stats are kept, next to the game. Runs add to what's already there.
+pub const STATS_PATH: &str = "playtest_stats.json";
+
+pub(super) fn plugin(app: &mut App) {
+    if !std::env::args().any(|arg| arg == "--playtest-stats") {
+        return;
+    }
+    info!("Recording playtest stats to {STATS_PATH}");
+
+    app.insert_resource(load_stats());
+    app.add_systems(
+        Update,
+        (count_time_played, count_commands).run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), save_stats);
+    app.add_systems(Last, save_stats.run_if(on_event::<AppExit>));
+}
+
+#[derive(Resource, Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(default)]
+struct PlaytestStats {
+    /// Seconds spent playing, by level
+    seconds_played: HashMap<String, f32>,
+    /// How many times each command was run, by name
+    command_uses: HashMap<String, u32>,
+    /// Lines run in the terminal, and how many of those failed
+    lines_run: u32,
+    lines_failed: u32,
+}
+
+impl PlaytestStats {
+    fn record(&mut self, ran: &CommandsRan) {
+        self.lines_run += 1;
+        if !ran.success {
+            self.lines_failed += 1;
+        }
+        for name in &ran.names {
+            *self.command_uses.entry(name.to_string()).or_default() += 1;
+        }
+    }
+}
+
+fn load_stats() -> PlaytestStats {
+    let Ok(json) = std::fs::read_to_string(STATS_PATH) else {
+        return PlaytestStats::default();
+    };
+    serde_json::from_str(&json).unwrap_or_else(|err| {
+        warn!("Starting {STATS_PATH} over, as it couldn't be read: {err}");
+        PlaytestStats::default()
+    })
+}
+
+fn save_stats(stats: Res<PlaytestStats>) {
+    let result = serde_json::to_string_pretty(&*stats)
+        .map_err(|err| err.to_string())
+        .and_then(|json| std::fs::write(STATS_PATH, json).map_err(|err| err.to_string()));
+    if let Err(err) = result {
+        warn!("Couldn't save {STATS_PATH}: {err}");
+    }
+}
+
+fn count_time_played(
+    mut stats: ResMut<PlaytestStats>,
+    level_assets: Res<LevelAssets>,
+    time: Res<Time>,
+) {
+    let level = level_assets
+        .graph
+        .path()
+        .map_or_else(|| "unknown".to_string(), ToString::to_string);
+    *stats.seconds_played.entry(level).or_default() += time.delta_secs();
+}
+
+fn count_commands(mut stats: ResMut<PlaytestStats>, mut commands_ran: EventReader<CommandsRan>) {
+    for ran in commands_ran.read() {
+        stats.record(ran);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let mut stats = PlaytestStats::default();
+        stats.record(&CommandsRan::new("ls; note secret stuff", true));
+        stats.record(&CommandsRan::new("ls && frobnicate", false));
+
+        assert_eq!(stats.lines_run, 2);
+        assert_eq!(stats.lines_failed, 1);
+        assert_eq!(stats.command_uses["ls"], 2);
+        assert_eq!(stats.command_uses["note"], 1);
+        assert_eq!(stats.command_uses["unknown"], 1);
+        // Only command names are kept
+        let json = serde_json::to_string(&stats).unwrap();
+        assert!(!json.contains("secret") && !json.contains("frobnicate"));
+        assert_eq!(serde_json::from_str::<PlaytestStats>(&json).unwrap(), stats);
+    }
+}
@@ -52,6 +52,14 @@ impl ResourceHandles {
     pub fn is_all_done(&self) -> bool {
         self.waiting.is_empty()
     }
+
+    /// Every tracked asset, loaded or not
+    pub fn handles(&self) -> impl Iterator<Item = &UntypedHandle> {
+        self.waiting
+            .iter()
+            .map(|(handle, _)| handle)
+            .chain(&self.finished)
+    }
 }
 
 fn load_resource_assets(world: &mut World) {
@@ -1,8 +1,12 @@
-use bevy::prelude::*;
+use bevy::{audio::Volume, prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Ambience>();
     app.register_type::<Music>();
     app.register_type::<SoundEffect>();
+    app.register_type::<Caption>();
+    app.register_type::<Captions>();
+    app.init_resource::<Captions>();
 
     app.add_systems(
         Update,
@@ -23,6 +27,26 @@ pub fn music(handle: Handle<AudioSource>) -> impl Bundle {
     (AudioPlayer(handle), PlaybackSettings::LOOP, Music)
 }
 
+/// An organizational marker component that should be added to a spawned [`AudioPlayer`] if it's in the
+/// general "ambience" category (e.g. server-room hum, office chatter, rain), mixed quietly under the music.
+///
+/// This can then be used to query for and operate on sounds in that category.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Ambience;
+
+/// How loud ambience plays relative to music.
+const AMBIENCE_VOLUME: f32 = 0.4;
+
+/// An ambience audio instance.
+pub fn ambience(handle: Handle<AudioSource>) -> impl Bundle {
+    (
+        AudioPlayer(handle),
+        PlaybackSettings::LOOP.with_volume(Volume::Linear(AMBIENCE_VOLUME)),
+        Ambience,
+    )
+}
+
 /// An organizational marker component that should be added to a spawned [`AudioPlayer`] if it's in the
 /// general "sound effect" category (e.g. footsteps, the sound of a magic spell, a door opening).
 ///
@@ -36,6 +60,17 @@ pub fn sound_effect(handle: Handle<AudioSource>) -> impl Bundle {
     (AudioPlayer(handle), PlaybackSettings::DESPAWN, SoundEffect)
 }
 
+/// A short description of what a meaningful sound is (e.g. "alarm rising"), so it can be
+/// captioned for players who can't hear it. Add it next to the [`AudioPlayer`].
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct Caption(pub String);
+
+/// Whether sounds with a [`Caption`] are described in the terminal as they start.
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
+pub struct Captions(pub bool);
+
 /// [`GlobalVolume`] doesn't apply to already-running audio entities, so this system will update them.
 fn apply_global_volume(
     global_volume: Res<GlobalVolume>,
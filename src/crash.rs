@@ -0,0 +1,93 @@
+//! Crash reports. If the game panics, a report with the panic message and the last few lines
+//! run in the terminal is written next to the game, and a "kernel panic" pointing at it is
+//! printed on the way out. Once the game has panicked there's no window left to draw a
+//! friendlier screen in.
+
+use std::{collections::VecDeque, sync::Mutex};
+#[cfg(not(target_family = "wasm"))]
+use std::{
+    panic::PanicHookInfo,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+
+// How many terminal lines make it into a report
+const RECENT_LINES: usize = 20;
+
+// The panic hook can't get at the world, so the terminal leaves its lines here as it goes
+static RECENT: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+pub(super) fn plugin(_app: &mut App) {
+    // Browsers already show panics in the console, and there's nowhere to write a file
+    #[cfg(not(target_family = "wasm"))]
+    {
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            write_crash_report(info);
+            default_hook(info);
+        }));
+    }
+}
+
+/// Remembers a line the player ran in the terminal, in case it's what crashed the game
+pub fn remember(line: &str) {
+    let Ok(mut recent) = RECENT.lock() else {
+        return;
+    };
+    if recent.len() == RECENT_LINES {
+        recent.pop_front();
+    }
+    recent.push_back(line.to_string());
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn write_crash_report(info: &PanicHookInfo) {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let path = format!("crash-{secs}.txt");
+    // Whatever panicked might have been holding the lock, but the lines are still good
+    let recent = RECENT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .iter()
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let report = crash_report(&info.to_string(), &recent);
+    match std::fs::write(&path, &report) {
+        Ok(()) => eprintln!("Kernel panic - not syncing: {info}\nCrash report written to {path}"),
+        Err(err) => {
+            eprintln!("Kernel panic - not syncing: couldn't write {path} ({err})\n{report}")
+        }
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn crash_report(panic: &str, recent: &[String]) -> String {
+    let mut report = format!(
+        "Bevy Jam 6 {} crashed\n\n{panic}\n\nLast terminal lines:\n",
+        env!("CARGO_PKG_VERSION")
+    );
+    for line in recent {
+        report.push_str(&format!("> {line}\n"));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crash_report() {
+        let report = crash_report(
+            "panicked at src/main.rs:1:1:\noops",
+            &["ls".to_string(), "note hi".to_string()],
+        );
+        assert!(report.contains("oops"));
+        assert!(report.ends_with("Last terminal lines:\n> ls\n> note hi\n"));
+    }
+}
@@ -0,0 +1,130 @@
+//! The assets the game can't run without (the font, key clicks, core levels and terminal text),
+//! built into the executable so the native jam build can ship as a single file. Files in
+//! `assets/` are still read first when they're there, so edits and hot reloading work as usual.
+
+use std::path::Path;
+
+use bevy::asset::io::{
+    AssetReader, AssetReaderError, AssetSource, AssetSourceBuilder, ErasedAssetReader, PathStream,
+    Reader, SliceReader,
+};
+use bevy::prelude::*;
+
+// Where the asset plugin looks by default
+const ASSET_PATH: &str = "assets";
+
+/// Built-in copies of essential assets, by path within `assets/`
+const EMBEDDED_ASSETS: [(&str, &[u8]); 9] = [
+    (
+        "fonts/VT323-Regular.ttf",
+        include_bytes!("../assets/fonts/VT323-Regular.ttf"),
+    ),
+    (
+        "audio/sound_effects/keypress-001.wav",
+        include_bytes!("../assets/audio/sound_effects/keypress-001.wav"),
+    ),
+    (
+        "audio/sound_effects/keypress-002.wav",
+        include_bytes!("../assets/audio/sound_effects/keypress-002.wav"),
+    ),
+    (
+        "audio/sound_effects/keypress-003.wav",
+        include_bytes!("../assets/audio/sound_effects/keypress-003.wav"),
+    ),
+    (
+        "levels/level_01.txt",
+        include_bytes!("../assets/levels/level_01.txt"),
+    ),
+    (
+        "levels/dev_01.txt",
+        include_bytes!("../assets/levels/dev_01.txt"),
+    ),
+    (
+        "levels/test01.txt",
+        include_bytes!("../assets/levels/test01.txt"),
+    ),
+    (
+        "text/terminal.strings",
+        include_bytes!("../assets/text/terminal.strings"),
+    ),
+    (
+        "text/wiki.strings",
+        include_bytes!("../assets/text/wiki.strings"),
+    ),
+];
+
+/// The default asset source, falling back to the built-in copies of anything missing on disk.
+/// Has to be registered before the asset plugin is added.
+pub fn asset_source() -> AssetSourceBuilder {
+    let mut disk_reader = AssetSource::get_default_reader(ASSET_PATH.to_string());
+    AssetSourceBuilder::platform_default(ASSET_PATH, None).with_reader(move || {
+        Box::new(FallbackReader {
+            disk: disk_reader(),
+        })
+    })
+}
+
+/// Whether there's a built-in copy of the asset at `path`
+pub fn is_embedded(path: &str) -> bool {
+    embedded(Path::new(path)).is_some()
+}
+
+fn embedded(path: &Path) -> Option<&'static [u8]> {
+    EMBEDDED_ASSETS
+        .iter()
+        .find(|(embedded_path, _)| Path::new(embedded_path) == path)
+        .map(|(_, bytes)| *bytes)
+}
+
+// Reads from disk, and from the executable when disk doesn't have it
+struct FallbackReader {
+    disk: Box<dyn ErasedAssetReader>,
+}
+
+impl AssetReader for FallbackReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<Box<dyn Reader + 'a>, AssetReaderError> {
+        match self.disk.read(path).await {
+            Err(AssetReaderError::NotFound(missing)) => {
+                let bytes = embedded(path).ok_or(AssetReaderError::NotFound(missing))?;
+                debug!("Using the built-in copy of {}", path.display());
+                Ok(Box::new(SliceReader::new(bytes)))
+            }
+            result => result,
+        }
+    }
+
+    async fn read_meta<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<dyn Reader + 'a>, AssetReaderError> {
+        self.disk.read_meta(path).await
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        self.disk.read_directory(path).await
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        self.disk.is_directory(path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::BUNDLED_LEVELS;
+
+    #[test]
+    fn test_bundled_levels_are_embedded() {
+        for level in BUNDLED_LEVELS {
+            assert!(
+                embedded(Path::new(level)).is_some(),
+                "{level} isn't embedded"
+            );
+        }
+        assert_eq!(embedded(Path::new("levels/missing.txt")), None);
+    }
+}
@@ -0,0 +1,212 @@
+//! How a network hangs together: which machines can reach each other, which ones it would fall
+//! apart without (choke points), and which ones the most traffic has to go through.
+
+use std::collections::VecDeque;
+
+use super::network::NetworkGraph;
+
+/// What analysing a network turned up. Assets are indices into `NetworkGraph::assets`.
+#[derive(Debug, Default, PartialEq)]
+pub struct NetworkAnalysis {
+    /// Groups of assets that can reach each other, each in level order
+    pub components: Vec<Vec<usize>>,
+    /// Assets whose loss would split the network they're in, in level order
+    pub articulation_points: Vec<usize>,
+    /// For each asset, the share of shortest routes between other assets that go through it
+    /// (betweenness centrality, from 0 to 1)
+    pub betweenness: Vec<f32>,
+}
+
+impl NetworkAnalysis {
+    pub fn of(graph: &NetworkGraph) -> Self {
+        Self {
+            components: components(graph),
+            articulation_points: articulation_points(graph),
+            betweenness: betweenness(graph),
+        }
+    }
+
+    /// Assets that any routes go through, busiest first (ties in level order)
+    pub fn busiest(&self) -> Vec<usize> {
+        let mut busiest = (0..self.betweenness.len())
+            .filter(|&asset| self.betweenness[asset] > 0.0)
+            .collect::<Vec<usize>>();
+        busiest.sort_by(|&a, &b| self.betweenness[b].total_cmp(&self.betweenness[a]));
+        busiest
+    }
+}
+
+fn components(graph: &NetworkGraph) -> Vec<Vec<usize>> {
+    let mut seen = vec![false; graph.assets.len()];
+    let mut components = Vec::new();
+    for start in 0..graph.assets.len() {
+        if seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut component = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(current) = queue.pop_front() {
+            component.push(current);
+            for &neighbor in graph.neighbors(current) {
+                if !seen[neighbor] {
+                    seen[neighbor] = true;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        component.sort_unstable();
+        components.push(component);
+    }
+    components
+}
+
+fn articulation_points(graph: &NetworkGraph) -> Vec<usize> {
+    let count = graph.assets.len();
+    let mut search = ChokeSearch {
+        discovered: vec![None; count],
+        low: vec![0; count],
+        choke: vec![false; count],
+        time: 0,
+    };
+    for root in 0..count {
+        if search.discovered[root].is_none() {
+            search.visit(graph, root, None);
+        }
+    }
+    (0..count).filter(|&asset| search.choke[asset]).collect()
+}
+
+// Tarjan's depth-first search: an asset is a choke point if something below it in the search
+// can't get back above it any other way
+struct ChokeSearch {
+    // When each asset was first reached
+    discovered: Vec<Option<usize>>,
+    // The earliest-reached asset each asset's part of the search can link back to
+    low: Vec<usize>,
+    choke: Vec<bool>,
+    time: usize,
+}
+
+impl ChokeSearch {
+    fn visit(&mut self, graph: &NetworkGraph, asset: usize, parent: Option<usize>) {
+        let discovered = self.time;
+        self.discovered[asset] = Some(discovered);
+        self.low[asset] = discovered;
+        self.time += 1;
+
+        let mut children = 0;
+        for &neighbor in graph.neighbors(asset) {
+            if Some(neighbor) == parent {
+                continue;
+            }
+            match self.discovered[neighbor] {
+                Some(reached) => self.low[asset] = self.low[asset].min(reached),
+                None => {
+                    children += 1;
+                    self.visit(graph, neighbor, Some(asset));
+                    self.low[asset] = self.low[asset].min(self.low[neighbor]);
+                    if parent.is_some() && self.low[neighbor] >= discovered {
+                        self.choke[asset] = true;
+                    }
+                }
+            }
+        }
+        // Where the search started, it's a choke point if it had to start over below it
+        if parent.is_none() && children > 1 {
+            self.choke[asset] = true;
+        }
+    }
+}
+
+// Brandes' algorithm, counting shortest routes out from every asset in turn
+fn betweenness(graph: &NetworkGraph) -> Vec<f32> {
+    let count = graph.assets.len();
+    let mut centrality = vec![0.0; count];
+    for source in 0..count {
+        // Assets in the order they were reached, and the ones just before each on a shortest route
+        let mut order = Vec::new();
+        let mut previous = vec![Vec::new(); count];
+        let mut routes = vec![0.0_f32; count];
+        let mut distance = vec![None; count];
+        routes[source] = 1.0;
+        distance[source] = Some(0);
+
+        let mut queue = VecDeque::from([source]);
+        while let Some(current) = queue.pop_front() {
+            order.push(current);
+            let Some(current_distance) = distance[current] else {
+                continue;
+            };
+            // Linking two machines twice doesn't make twice the routes
+            let mut neighbors = graph.neighbors(current).to_vec();
+            neighbors.sort_unstable();
+            neighbors.dedup();
+            for neighbor in neighbors {
+                if distance[neighbor].is_none() {
+                    distance[neighbor] = Some(current_distance + 1);
+                    queue.push_back(neighbor);
+                }
+                if distance[neighbor] == Some(current_distance + 1) {
+                    routes[neighbor] += routes[current];
+                    previous[neighbor].push(current);
+                }
+            }
+        }
+
+        // Furthest first, add up how much each asset's routes depend on the ones before it
+        let mut dependency = vec![0.0; count];
+        for &asset in order.iter().rev() {
+            for &before in &previous[asset] {
+                dependency[before] += routes[before] / routes[asset] * (1.0 + dependency[asset]);
+            }
+            if asset != source {
+                centrality[asset] += dependency[asset];
+            }
+        }
+    }
+
+    // Every pair was counted from both ends, so dividing by the ordered pairs (not counting the
+    // asset itself) gives the share
+    let pairs = (count.saturating_sub(1) * count.saturating_sub(2)) as f32;
+    if pairs > 0.0 {
+        for value in &mut centrality {
+            *value /= pairs;
+        }
+    }
+    centrality
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_analysis() {
+        // Two workstations and a server hanging off one router, and a machine on its own
+        let graph = NetworkGraph::parse(
+            "type pc ws01\ntype pc ws02\ntype router r01\ntype server s01\ntype pc lone\n\
+             link ws01 r01\nlink ws02 r01\nlink r01 s01\nlink ws01 r01",
+        )
+        .unwrap();
+        let analysis = NetworkAnalysis::of(&graph);
+
+        assert_eq!(analysis.components, vec![vec![0, 1, 2, 3], vec![4]]);
+        assert_eq!(analysis.articulation_points, vec![2]);
+        // Every route between the other three goes through r01: 3 of the 6 pairs of the rest
+        assert_eq!(analysis.betweenness, vec![0.0, 0.0, 0.5, 0.0, 0.0]);
+        assert_eq!(analysis.busiest(), vec![2]);
+    }
+
+    #[test]
+    fn test_loop_has_no_choke_points() {
+        let graph = NetworkGraph::parse(
+            "type router a\ntype router b\ntype router c\nlink a b\nlink b c\nlink c a",
+        )
+        .unwrap();
+        let analysis = NetworkAnalysis::of(&graph);
+        assert!(analysis.articulation_points.is_empty());
+        assert_eq!(analysis.components, vec![vec![0, 1, 2]]);
+        assert!(analysis.busiest().is_empty());
+    }
+}
@@ -0,0 +1,237 @@
+//! Imports Graphviz DOT files (`.dot`, `.gv`) as levels, so existing network diagrams can be
+//! played on.
+//!
+//! Only the everyday subset of DOT is understood: one statement per line (or several split by
+//! `;`), node statements with `[key=value, ...]` attributes, and `--`/`->` edge chains. Edges
+//! are links either way round, and nodes only mentioned in edges get the default type.
+
+use std::collections::HashMap;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::network::{
+    NetworkGraph, NetworkGraphAsset, NetworkGraphAssetType, NetworkGraphLoadError,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset_loader::<DotLoader>();
+}
+
+/// How DOT node attributes turn into asset types. Set per file with a `.meta` file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DotMapping {
+    /// The node attribute holding the asset type
+    pub type_attribute: String,
+    /// The asset type of nodes without that attribute
+    pub default_type: String,
+    /// Attribute values that mean one of our asset types (e.g. "workstation" -> "pc")
+    pub types: HashMap<String, String>,
+}
+
+impl Default for DotMapping {
+    fn default() -> Self {
+        Self {
+            type_attribute: "type".to_string(),
+            default_type: "pc".to_string(),
+            types: [
+                ("host", "pc"),
+                ("workstation", "pc"),
+                ("gateway", "router"),
+                ("cloud", "internet"),
+            ]
+            .into_iter()
+            .map(|(from, to)| (from.to_string(), to.to_string()))
+            .collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct DotLoader;
+
+impl AssetLoader for DotLoader {
+    type Asset = NetworkGraph;
+    type Settings = DotMapping;
+    type Error = NetworkGraphLoadError;
+
+    fn extensions(&self) -> &[&str] {
+        &["dot", "gv"]
+    }
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut string = String::new();
+        reader.read_to_string(&mut string).await?;
+
+        let _span = debug_span!("import_dot", path = %load_context.path().display()).entered();
+        parse_dot(&string, settings)
+    }
+}
+
+/// Builds a level from DOT source
+pub fn parse_dot(
+    source: &str,
+    mapping: &DotMapping,
+) -> Result<NetworkGraph, NetworkGraphLoadError> {
+    // Nodes in order of first mention, with their type attribute (if they ever get one) and
+    // the line they first showed up on
+    let mut nodes: Vec<(String, Option<String>, i32)> = Vec::new();
+    let mut edges: Vec<(String, String, i32)> = Vec::new();
+    let mut in_body = false;
+
+    for (line_index, line) in source.lines().enumerate() {
+        let line_number = line_index as i32 + 1;
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if line.starts_with('#') {
+            continue;
+        }
+
+        for statement in line.split(';').map(str::trim) {
+            // The header (`graph name {`) and closing brace aren't statements
+            let statement = match statement.split_once('{') {
+                Some((_, rest)) if !in_body => {
+                    in_body = true;
+                    rest.trim()
+                }
+                _ => statement,
+            };
+            let statement = statement.trim_end_matches('}').trim();
+            if !in_body || statement.is_empty() {
+                continue;
+            }
+
+            let (targets, attributes) = match statement.split_once('[') {
+                Some((targets, attributes)) => (
+                    targets.trim(),
+                    parse_attributes(attributes.trim_end_matches(']')),
+                ),
+                None => (statement, HashMap::new()),
+            };
+
+            // Defaults and graph-wide settings don't describe the network
+            if matches!(targets, "graph" | "node" | "edge")
+                || targets.starts_with("subgraph")
+                || (targets.contains('=') && !targets.contains("--") && !targets.contains("->"))
+            {
+                continue;
+            }
+
+            let ids = targets
+                .split("--")
+                .flat_map(|part| part.split("->"))
+                .map(unquote)
+                .collect::<Vec<String>>();
+            if ids.iter().any(String::is_empty) {
+                return Err(NetworkGraphLoadError::ParseError(
+                    line_number,
+                    format!("Bad DOT statement: {statement}"),
+                ));
+            }
+
+            for id in &ids {
+                if !nodes.iter().any(|(name, ..)| name == id) {
+                    nodes.push((id.clone(), None, line_number));
+                }
+            }
+            // Only node statements (not edges) say what a node is
+            if let ([id], Some(asset_type)) =
+                (ids.as_slice(), attributes.get(&mapping.type_attribute))
+            {
+                for node in nodes.iter_mut().filter(|(name, ..)| name == id) {
+                    node.1 = Some(asset_type.clone());
+                }
+            }
+            for pair in ids.windows(2) {
+                edges.push((pair[0].clone(), pair[1].clone(), line_number));
+            }
+        }
+    }
+
+    let mut graph = NetworkGraph::default();
+    for (name, asset_type, line_number) in nodes {
+        let asset_type = asset_type.unwrap_or_else(|| mapping.default_type.clone());
+        let asset_type = mapping.types.get(&asset_type).unwrap_or(&asset_type);
+        graph.add_asset(NetworkGraphAsset {
+            asset_type: NetworkGraphAssetType::from_str(asset_type, Vec::new()).map_err(|err| {
+                NetworkGraphLoadError::ObjectParseError(line_number, asset_type.clone(), err)
+            })?,
+            name,
+        });
+    }
+    for (from, to, line_number) in edges {
+        // Every name in an edge was added as a node above
+        let (Some(from_index), Some(to_index)) = (graph.asset_index(&from), graph.asset_index(&to))
+        else {
+            return Err(NetworkGraphLoadError::BadLinkError(
+                line_number,
+                format!("{from} -- {to}"),
+            ));
+        };
+        graph.add_link(from_index, to_index);
+    }
+
+    Ok(graph)
+}
+
+// `key=value, key2="value 2"` -> map
+fn parse_attributes(attributes: &str) -> HashMap<String, String> {
+    attributes
+        .split(',')
+        .filter_map(|attribute| attribute.split_once('='))
+        .map(|(key, value)| (unquote(key), unquote(value)))
+        .collect()
+}
+
+fn unquote(id: &str) -> String {
+    id.trim().trim_matches('"').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dot() {
+        let graph = parse_dot(
+            r#"
+            // A tiny office
+            graph office {
+                node [shape=box];
+                "ws01" [type=workstation, label="Front desk"];
+                r01 [type=router];
+                ws01 -- r01 -> i01;
+                i01 [type=cloud]
+            }
+            "#,
+            &DotMapping::default(),
+        )
+        .unwrap();
+
+        let names = graph
+            .assets
+            .iter()
+            .map(|asset| (asset.name.as_str(), asset.asset_type.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![("ws01", "pc"), ("r01", "router"), ("i01", "internet")]
+        );
+        assert_eq!(graph.links, vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn test_parse_dot_unknown_type() {
+        let result = parse_dot("graph { a [type=toaster] }", &DotMapping::default());
+        assert!(matches!(
+            result,
+            Err(NetworkGraphLoadError::ObjectParseError(..))
+        ));
+    }
+}
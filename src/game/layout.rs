@@ -0,0 +1,303 @@
+//! Split between the graph view (top) and the terminal (bottom) on the gameplay screen,
+//! including popping the terminal out into its own window on native builds.
+
+#[cfg(not(target_family = "wasm"))]
+use bevy::{
+    input::common_conditions::input_just_pressed, render::camera::RenderTarget, window::WindowRef,
+};
+use bevy::{prelude::*, ui::Val::*, window::PrimaryWindow};
+
+use crate::{screens::Screen, terminal::TerminalWindow};
+
+const SPLIT_HANDLE_HEIGHT: f32 = 6.0;
+const SPLIT_KEY_STEP: f32 = 0.1;
+const MAX_GRAPH_RATIO: f32 = 0.9;
+#[cfg(not(target_family = "wasm"))]
+const POP_OUT_KEY: KeyCode = KeyCode::F10;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PanelSplit>();
+    app.register_type::<GraphPanel>();
+    app.register_type::<TerminalPanel>();
+    app.register_type::<SplitHandle>();
+    app.init_resource::<PanelSplit>();
+
+    app.add_observer(drag_split_handle);
+    app.add_systems(
+        Update,
+        (
+            resize_split_on_key_press,
+            apply_panel_split.run_if(
+                resource_changed::<PanelSplit>
+                    .or(resource_exists_and_changed::<DetachedTerminal>)
+                    .or(resource_removed::<DetachedTerminal>),
+            ),
+        )
+            .chain()
+            .run_if(in_state(Screen::Gameplay)),
+    );
+
+    // Pop the terminal out into its own window (native only).
+    #[cfg(not(target_family = "wasm"))]
+    app.add_systems(
+        Update,
+        (
+            toggle_terminal_window.run_if(input_just_pressed(POP_OUT_KEY)),
+            reattach_closed_terminal_window.run_if(resource_exists::<DetachedTerminal>),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), forget_detached_terminal);
+}
+
+/// How much of the gameplay screen the graph view takes up, from `0.0` (collapsed) to
+/// [`MAX_GRAPH_RATIO`]. The terminal gets the rest.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct PanelSplit {
+    pub graph_ratio: f32,
+}
+
+impl Default for PanelSplit {
+    fn default() -> Self {
+        Self { graph_ratio: 0.5 }
+    }
+}
+
+impl PanelSplit {
+    /// Moves the split by `delta` (a fraction of the screen height), staying within bounds.
+    pub fn nudge(&mut self, delta: f32) {
+        self.graph_ratio = (self.graph_ratio + delta).clamp(0.0, MAX_GRAPH_RATIO);
+    }
+
+    fn graph_height(&self) -> Val {
+        Percent(self.graph_ratio * 100.0)
+    }
+
+    fn terminal_height(&self) -> Val {
+        Percent((1.0 - self.graph_ratio) * 100.0)
+    }
+}
+
+/// The terminal while it's popped out into its own OS window.
+#[derive(Resource, Debug)]
+struct DetachedTerminal {
+    window: Entity,
+    camera: Entity,
+    terminal: Entity,
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct GraphPanel;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct TerminalPanel;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct SplitHandle;
+
+/// The top panel holding the graph view.
+pub fn graph_panel(split: &PanelSplit) -> impl Bundle {
+    (
+        Name::new("Graph Panel"),
+        GraphPanel,
+        Node {
+            height: split.graph_height(),
+            overflow: Overflow::clip(),
+            ..default()
+        },
+    )
+}
+
+/// A bar between the panels that can be dragged to resize them.
+pub fn split_handle() -> impl Bundle {
+    (
+        Name::new("Split Handle"),
+        SplitHandle,
+        BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+        Node {
+            flex_shrink: 0.0,
+            height: Px(SPLIT_HANDLE_HEIGHT),
+            width: Percent(100.0),
+            ..default()
+        },
+    )
+}
+
+/// The bottom panel holding the terminal.
+pub fn terminal_panel(split: &PanelSplit) -> impl Bundle {
+    (
+        Name::new("Terminal Panel"),
+        TerminalPanel,
+        Node {
+            height: split.terminal_height(),
+            ..default()
+        },
+    )
+}
+
+fn drag_split_handle(
+    trigger: Trigger<Pointer<Drag>>,
+    handle_query: Query<(), With<SplitHandle>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut split: ResMut<PanelSplit>,
+) {
+    if !handle_query.contains(trigger.target()) || window.height() <= 0.0 {
+        return;
+    }
+
+    split.nudge(trigger.event().delta.y / window.height());
+}
+
+// Ctrl+Up shrinks the graph (all the way down to collapsed), Ctrl+Down grows it
+fn resize_split_on_key_press(input: Res<ButtonInput<KeyCode>>, mut split: ResMut<PanelSplit>) {
+    if !input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        return;
+    }
+
+    if input.just_pressed(KeyCode::ArrowUp) {
+        split.nudge(-SPLIT_KEY_STEP);
+    }
+    if input.just_pressed(KeyCode::ArrowDown) {
+        split.nudge(SPLIT_KEY_STEP);
+    }
+}
+
+fn apply_panel_split(
+    split: Res<PanelSplit>,
+    detached: Option<Res<DetachedTerminal>>,
+    mut graph_query: Query<&mut Node, (With<GraphPanel>, Without<TerminalPanel>)>,
+    mut terminal_query: Query<
+        (&mut Node, Has<SplitHandle>),
+        (
+            Or<(With<TerminalPanel>, With<SplitHandle>)>,
+            Without<GraphPanel>,
+        ),
+    >,
+) {
+    // With the terminal in its own window, the graph gets the whole screen
+    let detached = detached.is_some();
+
+    for mut node in &mut graph_query {
+        node.height = if detached {
+            Percent(100.0)
+        } else {
+            split.graph_height()
+        };
+    }
+    for (mut node, is_handle) in &mut terminal_query {
+        node.display = if detached {
+            Display::None
+        } else {
+            Display::Flex
+        };
+        if !is_handle {
+            node.height = split.terminal_height();
+        }
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn toggle_terminal_window(
+    mut commands: Commands,
+    detached: Option<Res<DetachedTerminal>>,
+    terminal_panel: Single<(Entity, Option<&Children>), With<TerminalPanel>>,
+    mut terminal_window: ResMut<TerminalWindow>,
+) {
+    let (panel, children) = terminal_panel.into_inner();
+
+    if let Some(detached) = detached {
+        reattach_terminal(&mut commands, &detached, panel, &mut terminal_window);
+        return;
+    }
+
+    let Some(&terminal) = children.and_then(|children| children.first()) else {
+        return;
+    };
+
+    let window = commands
+        .spawn((
+            Name::new("Terminal Window"),
+            Window {
+                title: "Bevy Jam 6 - Terminal".to_string(),
+                ..default()
+            },
+            StateScoped(Screen::Gameplay),
+        ))
+        .id();
+    let camera = commands
+        .spawn((
+            Name::new("Terminal Camera"),
+            Camera2d,
+            Camera {
+                target: RenderTarget::Window(WindowRef::Entity(window)),
+                clear_color: ClearColorConfig::Custom(Color::BLACK),
+                ..default()
+            },
+            StateScoped(Screen::Gameplay),
+        ))
+        .id();
+
+    // UI roots can be rendered by any camera, so make the terminal a root of its own
+    commands
+        .entity(terminal)
+        .remove::<ChildOf>()
+        .insert((UiTargetCamera(camera), StateScoped(Screen::Gameplay)));
+
+    terminal_window.0 = Some(window);
+    commands.insert_resource(DetachedTerminal {
+        window,
+        camera,
+        terminal,
+    });
+}
+
+// Closing the terminal window puts the terminal back where it was
+#[cfg(not(target_family = "wasm"))]
+fn reattach_closed_terminal_window(
+    mut commands: Commands,
+    detached: Res<DetachedTerminal>,
+    window_query: Query<(), With<Window>>,
+    terminal_panel: Single<Entity, With<TerminalPanel>>,
+    mut terminal_window: ResMut<TerminalWindow>,
+) {
+    if window_query.contains(detached.window) {
+        return;
+    }
+
+    reattach_terminal(
+        &mut commands,
+        &detached,
+        *terminal_panel,
+        &mut terminal_window,
+    );
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn reattach_terminal(
+    commands: &mut Commands,
+    detached: &DetachedTerminal,
+    panel: Entity,
+    terminal_window: &mut TerminalWindow,
+) {
+    commands
+        .entity(detached.terminal)
+        .remove::<(UiTargetCamera, StateScoped<Screen>)>();
+    commands.entity(panel).add_child(detached.terminal);
+    commands.entity(detached.camera).despawn();
+    if let Ok(mut window) = commands.get_entity(detached.window) {
+        window.despawn();
+    }
+
+    terminal_window.0 = None;
+    commands.remove_resource::<DetachedTerminal>();
+}
+
+// Detached windows are state scoped, so only the bookkeeping needs resetting
+fn forget_detached_terminal(mut commands: Commands, mut terminal_window: ResMut<TerminalWindow>) {
+    terminal_window.0 = None;
+    commands.remove_resource::<DetachedTerminal>();
+}
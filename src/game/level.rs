@@ -0,0 +1,128 @@
+//! The level being played.
+
+#[cfg(not(target_family = "wasm"))]
+use bevy::asset::io::file::FileAssetReader;
+use bevy::prelude::*;
+
+use crate::{
+    asset_tracking::LoadResource,
+    audio::{Caption, ambience},
+    game::network::NetworkGraph,
+    launch::LaunchConfig,
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<LevelAssets>();
+    app.load_resource::<LevelAssets>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), start_ambience);
+}
+
+const LEVEL_PATH: &str = "levels/level_01.txt";
+
+/// Every level that ships in `assets/levels`, for the self-test to check
+pub const BUNDLED_LEVELS: [&str; 3] = [LEVEL_PATH, "levels/dev_01.txt", "levels/test01.txt"];
+
+/// Assets for the current level. Loading (and preprocessing) the network graph happens on the
+/// asset threads during the loading screen, so gameplay starts with it ready to go.
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct LevelAssets {
+    #[dependency]
+    pub graph: Handle<NetworkGraph>,
+}
+
+impl FromWorld for LevelAssets {
+    fn from_world(world: &mut World) -> Self {
+        // A level picked at launch replaces the usual first level, as long as there is one.
+        // Otherwise the loading screen would wait on it forever.
+        let path = world
+            .get_resource::<LaunchConfig>()
+            .and_then(LaunchConfig::level_path)
+            .filter(|path| {
+                let found = level_exists(path);
+                if !found {
+                    error!("Can't find level {path}, so starting in {LEVEL_PATH} instead");
+                }
+                found
+            })
+            .unwrap_or_else(|| LEVEL_PATH.to_string());
+        let assets = world.resource::<AssetServer>();
+        Self {
+            graph: assets.load(path),
+        }
+    }
+}
+
+// Whether a level is there to load, built in or on disk
+#[cfg(not(target_family = "wasm"))]
+fn level_exists(path: &str) -> bool {
+    #[cfg(feature = "embedded_assets")]
+    if crate::embedded_assets::is_embedded(path) {
+        return true;
+    }
+    // `assets` is the asset plugin's default folder
+    FileAssetReader::get_base_path()
+        .join("assets")
+        .join(path)
+        .is_file()
+}
+
+// Browsers could only find out by asking the server, so the web build takes the level on trust
+#[cfg(target_family = "wasm")]
+fn level_exists(_path: &str) -> bool {
+    true
+}
+
+fn start_ambience(
+    mut commands: Commands,
+    level_assets: Res<LevelAssets>,
+    graphs: Res<Assets<NetworkGraph>>,
+) {
+    let Some(graph) = graphs.get(&level_assets.graph) else {
+        return;
+    };
+
+    for handle in &graph.ambience {
+        commands.spawn((
+            Name::new("Level Ambience"),
+            StateScoped(Screen::Gameplay),
+            ambience(handle.clone()),
+            ambience_caption(handle),
+        ));
+    }
+}
+
+// Ambience files are named for what they sound like (`server_room_hum.ogg` -> "server room hum")
+fn ambience_caption(handle: &Handle<AudioSource>) -> Caption {
+    let description = handle
+        .path()
+        .and_then(|path| path.path().file_stem())
+        .map(|stem| stem.to_string_lossy().replace(['_', '-'], " "))
+        .unwrap_or_else(|| "ambience".to_string());
+    Caption(description)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_levels_parse() {
+        for path in BUNDLED_LEVELS {
+            let source =
+                std::fs::read_to_string(format!("{}/assets/{path}", env!("CARGO_MANIFEST_DIR")))
+                    .unwrap();
+            if let Err(err) = NetworkGraph::parse(&source) {
+                panic!("{path}: {err}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_level_exists() {
+        assert!(BUNDLED_LEVELS.into_iter().all(level_exists));
+        assert!(!level_exists("levels/level_99.txt"));
+    }
+}
@@ -1,12 +1,34 @@
+mod analysis;
+mod dot_import;
+mod layout;
+mod level;
+mod network;
+mod observer;
+mod sandbox;
+
+pub use analysis::NetworkAnalysis;
 use bevy::prelude::*;
+pub use layout::PanelSplit;
+use layout::{graph_panel, split_handle, terminal_panel};
+pub use level::{BUNDLED_LEVELS, LevelAssets};
+pub use network::{NetworkGraph, NetworkGraphAsset, NetworkGraphAssetType};
+pub use observer::{ObserverMode, observing};
+pub use sandbox::Sandbox;
 
 use crate::{
     screens::Screen,
-    terminal::{TerminalAssets, terminal},
+    terminal::{TerminalAssets, TerminalAutotype, notes_panel, terminal},
 };
 
-pub fn spawn_level(mut commands: Commands, terminal_assets: Res<TerminalAssets>) {
-    commands.spawn((
+pub fn spawn_level(
+    mut commands: Commands,
+    terminal_assets: Res<TerminalAssets>,
+    split: Res<PanelSplit>,
+    observer_mode: Res<ObserverMode>,
+    level_assets: Res<LevelAssets>,
+    graphs: Res<Assets<NetworkGraph>>,
+) {
+    let mut level = commands.spawn((
         BackgroundColor(Color::BLACK),
         Node {
             display: Display::Flex,
@@ -17,21 +39,31 @@ pub fn spawn_level(mut commands: Commands, terminal_assets: Res<TerminalAssets>)
         },
         StateScoped(Screen::Gameplay),
         children![
-            Node {
-                height: Val::Percent(50.0),
-                ..default()
-            },
+            (graph_panel(&split), children![notes_panel()]),
+            split_handle(),
             (
-                Node {
-                    height: Val::Percent(50.0),
-                    ..default()
-                },
+                terminal_panel(&split),
                 children![terminal(&terminal_assets)]
             )
         ],
     ));
+
+    // Observers watch a scripted player at the terminal instead of typing themselves
+    if observer_mode.0 {
+        let script = graphs
+            .get(&level_assets.graph)
+            .map_or_else(Vec::new, observer::observer_script);
+        level.insert(TerminalAutotype::new(script));
+    }
 }
 
-pub(super) fn plugin(_app: &mut App) {
-    // TODO
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins((
+        layout::plugin,
+        network::plugin,
+        dot_import::plugin,
+        level::plugin,
+        observer::plugin,
+        sandbox::plugin,
+    ));
 }
@@ -0,0 +1,670 @@
+//! The network graph a level is played on, and its text level format.
+//!
+//! A level file has one directive per line, and `#` starts a comment:
+//! - `version <n>`, first if it's there
+//! - `type <type> <name>` for a machine, or `template <name>: <type> <key=value>...` and
+//!   `node <name> from <template> <key=value>...` for lots of similar ones
+//! - `link <name> <name>`
+//! - `allow <command>...` and `deny <command>...` for which commands the level lets the player use
+//! - `include <path>` to pull in another level file's directives
+//! - `ambience <path>` for an audio loop to play under the music while the level runs. Its file
+//!   name is its caption, so `server_room_hum.ogg` reads "server room hum".
+
+// Unused utilities may trigger this lint undesirably.
+#![allow(dead_code)]
+
+use std::collections::{HashMap, VecDeque};
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use thiserror::Error;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<NetworkGraph>();
+    app.init_asset::<NetworkGraph>();
+    app.init_asset_loader::<NetworkGraph>();
+}
+
+/// Version of the text level format this build understands. Files without a `version` line
+/// predate it and are read as version 1.
+pub const LEVEL_FORMAT_VERSION: u32 = 1;
+
+#[derive(Reflect)]
+pub enum NetworkGraphAssetType {
+    Pc(),
+    Router(),
+    Switch(),
+    Server(),
+    Firewall(),
+    Internet(),
+}
+
+impl NetworkGraphAssetType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            NetworkGraphAssetType::Pc() => "pc",
+            NetworkGraphAssetType::Router() => "router",
+            NetworkGraphAssetType::Switch() => "switch",
+            NetworkGraphAssetType::Server() => "server",
+            NetworkGraphAssetType::Firewall() => "firewall",
+            NetworkGraphAssetType::Internet() => "internet",
+        }
+    }
+    pub fn from_str(s: &str, _params: Vec<String>) -> Result<Self, String> {
+        match s {
+            "pc" => Ok(NetworkGraphAssetType::Pc()),
+            "router" => Ok(NetworkGraphAssetType::Router()),
+            "switch" => Ok(NetworkGraphAssetType::Switch()),
+            "server" => Ok(NetworkGraphAssetType::Server()),
+            "firewall" => Ok(NetworkGraphAssetType::Firewall()),
+            "internet" => Ok(NetworkGraphAssetType::Internet()),
+            _ => Err("Unknown asset type".to_string()),
+        }
+    }
+}
+
+#[derive(Reflect)]
+pub struct NetworkGraphAsset {
+    pub asset_type: NetworkGraphAssetType,
+    pub name: String,
+}
+
+#[derive(Resource, Asset, Reflect, Default)]
+#[reflect(Resource)]
+pub struct NetworkGraph {
+    pub assets: Vec<NetworkGraphAsset>,
+    pub links: Vec<(usize, usize)>, // Links between assets, represented as tuples of indices into the assets property
+    // Commands the level lets the player use (`None` means all of them), minus any denied ones
+    pub allowed_commands: Option<Vec<String>>,
+    pub denied_commands: Vec<String>,
+    // Ambient loops played under the music while the level is running
+    ambience_paths: Vec<String>,
+    #[dependency]
+    pub ambience: Vec<Handle<AudioSource>>,
+    // Built once at load time (off the main thread) so gameplay doesn't have to
+    asset_indices: HashMap<String, usize>,
+    neighbors: Vec<Vec<usize>>,
+    // Asset templates by name: their type and default params
+    pub templates: HashMap<String, (String, Vec<String>)>,
+}
+
+impl NetworkGraph {
+    /// Index into `assets` of the asset with the given name
+    pub fn asset_index(&self, name: &str) -> Option<usize> {
+        self.asset_indices.get(name).copied()
+    }
+
+    /// Indices of every asset linked to the given one
+    pub fn neighbors(&self, index: usize) -> &[usize] {
+        self.neighbors.get(index).map_or(&[], Vec::as_slice)
+    }
+
+    /// Shortest chain of assets from `from` to `to` (both included), if they're connected
+    pub fn path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let mut previous = vec![None; self.assets.len()];
+        let mut queue = VecDeque::from([from]);
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                let mut path = vec![to];
+                while let Some(prev) = previous[*path.last()?] {
+                    path.push(prev);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for &neighbor in self.neighbors(current) {
+                if neighbor != from && previous[neighbor].is_none() {
+                    previous[neighbor] = Some(current);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        None
+    }
+
+    /// The topology as a Graphviz DOT graph, with each asset's type as an attribute
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph network {\n");
+        for asset in &self.assets {
+            dot.push_str(&format!(
+                "    \"{}\" [type={}];\n",
+                asset.name,
+                asset.asset_type.as_str()
+            ));
+        }
+        for &(from, to) in &self.links {
+            if let (Some(from), Some(to)) = (self.assets.get(from), self.assets.get(to)) {
+                dot.push_str(&format!("    \"{}\" -- \"{}\";\n", from.name, to.name));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Whether the level lets the player use the command with the given name
+    pub fn command_enabled(&self, name: &str) -> bool {
+        let allowed = self
+            .allowed_commands
+            .as_ref()
+            .is_none_or(|allowed| allowed.iter().any(|command| command == name));
+        allowed && !self.denied_commands.iter().any(|command| command == name)
+    }
+
+    pub(super) fn add_asset(&mut self, asset: NetworkGraphAsset) {
+        self.asset_indices
+            .insert(asset.name.clone(), self.assets.len());
+        self.assets.push(asset);
+        self.neighbors.push(Vec::new());
+    }
+
+    pub(super) fn add_link(&mut self, from: usize, to: usize) {
+        self.links.push((from, to));
+        self.neighbors[from].push(to);
+        self.neighbors[to].push(from);
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum NetworkGraphLoadError {
+    #[error("Io Error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Error: Line {0}: {1}")]
+    ParseError(i32 /* line number */, String),
+    #[error("ObjectParseError: line: {0}, Object {1}: {2}")]
+    ObjectParseError(i32 /* line number */, String, String),
+    #[error("Invalid directive at line {0}: {1}")]
+    InvalidDirective(i32 /* line number */, String),
+    #[error("Bad link at line {0}: {1}")]
+    BadLinkError(i32 /* line number */, String),
+    #[error(
+        "Line {0}: level format version {1} is newer than this game understands ({LEVEL_FORMAT_VERSION})"
+    )]
+    UnsupportedVersion(i32 /* line number */, u32),
+    #[error("Line {0}: can't include {1}: {2}")]
+    IncludeError(i32 /* line number */, String, String),
+    #[error("Line {0}: in included file {1}: {2}")]
+    InIncludedFile(i32 /* line number */, String, Box<Self>),
+}
+
+impl AssetLoader for NetworkGraph {
+    type Asset = NetworkGraph;
+    type Settings = ();
+    type Error = NetworkGraphLoadError;
+
+    fn extensions(&self) -> &[&str] {
+        &["txt"]
+    }
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut string = String::new();
+        reader.read_to_string(&mut string).await?;
+
+        // Included files are read up front (and watched for hot reloading) so parsing itself
+        // doesn't have to be async
+        let mut includes = HashMap::new();
+        let mut pending = include_paths(&string).collect::<Vec<String>>();
+        while let Some(path) = pending.pop() {
+            if includes.contains_key(&path) {
+                continue;
+            }
+            let source = match load_context.read_asset_bytes(path.clone()).await {
+                Ok(bytes) => String::from_utf8(bytes).map_err(|err| err.to_string()),
+                Err(err) => Err(err.to_string()),
+            };
+            if let Ok(source) = &source {
+                pending.extend(include_paths(source));
+            }
+            includes.insert(path, source);
+        }
+
+        // Entered spans can't be held across an await, so only start timing once the files are read
+        let _span =
+            debug_span!("load_network_graph", path = %load_context.path().display()).entered();
+
+        let mut graph = NetworkGraph::parse_with_includes(&string, &includes)?;
+        graph.ambience = graph
+            .ambience_paths
+            .iter()
+            .map(|path| load_context.load(path))
+            .collect();
+
+        Ok(graph)
+    }
+}
+
+impl NetworkGraph {
+    /// Parses a level from its text format
+    pub fn parse(string: &str) -> Result<NetworkGraph, NetworkGraphLoadError> {
+        Self::parse_with_includes(string, &HashMap::new())
+    }
+
+    /// Parses a level whose `include`d files (by asset path) have already been read
+    pub fn parse_with_includes(
+        string: &str,
+        includes: &HashMap<String, Result<String, String>>,
+    ) -> Result<NetworkGraph, NetworkGraphLoadError> {
+        let mut graph = NetworkGraph::default();
+        graph.parse_source(string, includes, &mut Vec::new())?;
+        Ok(graph)
+    }
+
+    // Adds one file's directives to the graph. `including` is the chain of files being
+    // included, to catch cycles.
+    fn parse_source(
+        &mut self,
+        string: &str,
+        includes: &HashMap<String, Result<String, String>>,
+        including: &mut Vec<String>,
+    ) -> Result<(), NetworkGraphLoadError> {
+        let graph = self;
+        let mut line_number = 0;
+        let mut seen_directive = false;
+        for line in string.lines() {
+            line_number += 1;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue; // Skip empty lines and comments
+            }
+            let first_directive = !seen_directive;
+            seen_directive = true;
+
+            if trimmed.starts_with("version") {
+                // Which revision of this format the file was written for. It has to come first,
+                // so everything after it can be read the way that version meant it.
+                let parts: Vec<&str> = trimmed.split_whitespace().collect();
+                let version = match parts.as_slice() {
+                    [_, version] if first_directive => version.parse::<u32>().ok(),
+                    _ => None,
+                }
+                .ok_or_else(|| {
+                    NetworkGraphLoadError::ParseError(
+                        line_number,
+                        "Invalid version declaration (must be `version <number>` on the first line)"
+                            .to_string(),
+                    )
+                })?;
+                if version > LEVEL_FORMAT_VERSION {
+                    return Err(NetworkGraphLoadError::UnsupportedVersion(
+                        line_number,
+                        version,
+                    ));
+                }
+                debug!("Level format version {version}");
+            } else if let Some(path) = trimmed.strip_prefix("include ") {
+                // Pull in another level file (shared templates, common services) as if its
+                // directives were written here
+                let path = path.trim().to_string();
+                let include_error = |reason: &str| {
+                    NetworkGraphLoadError::IncludeError(
+                        line_number,
+                        path.clone(),
+                        reason.to_string(),
+                    )
+                };
+                if including.contains(&path) {
+                    return Err(include_error("it includes this file (include cycle)"));
+                }
+                let source = match includes.get(&path) {
+                    Some(Ok(source)) => source,
+                    Some(Err(err)) => return Err(include_error(err)),
+                    None => return Err(include_error("not found")),
+                };
+                including.push(path.clone());
+                graph
+                    .parse_source(source, includes, including)
+                    .map_err(|err| {
+                        NetworkGraphLoadError::InIncludedFile(
+                            line_number,
+                            path.clone(),
+                            Box::new(err),
+                        )
+                    })?;
+                including.pop();
+                debug!("Included {path}");
+            } else if trimmed.starts_with("type") {
+                // Handle object types
+                let parts: Vec<&str> = trimmed.split_whitespace().collect();
+                if parts.len() < 3 {
+                    return Err(NetworkGraphLoadError::ParseError(
+                        line_number,
+                        "Invalid type declaration".to_string(),
+                    ));
+                }
+                let object_type = parts[1];
+                let object_name = parts[2];
+                let params = parts[3..].iter().map(|s| s.to_string()).collect();
+                graph.add_parsed_asset(line_number, object_type, object_name, params)?;
+                debug!("Found object type: {object_type} with name: {object_name}");
+            } else if let Some(declaration) = trimmed.strip_prefix("template ") {
+                // A reusable asset: `template <name>: <type> [key=value...]`
+                let (template_name, rest) = declaration.split_once(':').unwrap_or_default();
+                let template_name = template_name.trim();
+                let parts: Vec<&str> = rest.split_whitespace().collect();
+                let (false, [object_type, params @ ..]) =
+                    (template_name.is_empty(), parts.as_slice())
+                else {
+                    return Err(NetworkGraphLoadError::ParseError(
+                        line_number,
+                        "Invalid template declaration (expected `template <name>: <type>`)"
+                            .to_string(),
+                    ));
+                };
+                graph.templates.insert(
+                    template_name.to_string(),
+                    (
+                        object_type.to_string(),
+                        params.iter().map(|s| s.to_string()).collect(),
+                    ),
+                );
+                debug!("Found template {template_name} of type {object_type}");
+            } else if trimmed.starts_with("node") {
+                // An asset made from a template: `node <name> from <template> [key=value...]`,
+                // where the given params replace the template's ones with the same key
+                let parts: Vec<&str> = trimmed.split_whitespace().collect();
+                let [_, object_name, "from", template_name, overrides @ ..] = parts.as_slice()
+                else {
+                    return Err(NetworkGraphLoadError::ParseError(
+                        line_number,
+                        "Invalid node declaration (expected `node <name> from <template>`)"
+                            .to_string(),
+                    ));
+                };
+                let (object_type, mut params) = graph
+                    .templates
+                    .get(*template_name)
+                    .cloned()
+                    .ok_or_else(|| {
+                        NetworkGraphLoadError::ParseError(
+                            line_number,
+                            format!("Unknown template: {template_name}"),
+                        )
+                    })?;
+                for param in overrides {
+                    let key = param.split('=').next().unwrap_or_default();
+                    params.retain(|existing| existing.split('=').next() != Some(key));
+                    params.push(param.to_string());
+                }
+                graph.add_parsed_asset(line_number, &object_type, object_name, params)?;
+                debug!("Found {object_type} {object_name} from template {template_name}");
+            } else if trimmed.starts_with("link") {
+                // Handle links
+                let parts: Vec<&str> = trimmed.split_whitespace().collect();
+                if parts.len() < 3 {
+                    return Err(NetworkGraphLoadError::BadLinkError(
+                        line_number,
+                        "Invalid link declaration".to_string(),
+                    ));
+                }
+                let from = parts[1];
+                let to = parts[2];
+                let from_index = graph.asset_index(from).ok_or_else(|| {
+                    NetworkGraphLoadError::BadLinkError(
+                        line_number,
+                        format!("Unknown asset: {}", from),
+                    )
+                })?;
+                let to_index = graph.asset_index(to).ok_or_else(|| {
+                    NetworkGraphLoadError::BadLinkError(
+                        line_number,
+                        format!("Unknown asset: {}", to),
+                    )
+                })?;
+                graph.add_link(from_index, to_index);
+                debug!("Found link from {from} to {to}");
+            } else if trimmed.starts_with("allow") {
+                // Only these commands (and no others) are available on this level
+                graph
+                    .allowed_commands
+                    .get_or_insert_default()
+                    .extend(trimmed.split_whitespace().skip(1).map(|s| s.to_string()));
+            } else if trimmed.starts_with("ambience") {
+                // Ambient loops, loaded alongside the level
+                let parts: Vec<&str> = trimmed.split_whitespace().collect();
+                if parts.len() < 2 {
+                    return Err(NetworkGraphLoadError::ParseError(
+                        line_number,
+                        "Invalid ambience declaration".to_string(),
+                    ));
+                }
+                graph.ambience_paths.push(parts[1..].join(" "));
+            } else if trimmed.starts_with("deny") {
+                // These commands aren't available on this level
+                graph
+                    .denied_commands
+                    .extend(trimmed.split_whitespace().skip(1).map(|s| s.to_string()));
+            } else {
+                return Err(NetworkGraphLoadError::InvalidDirective(
+                    line_number,
+                    trimmed.to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl NetworkGraph {
+    // Adds an asset declared on the given line, checking its name is free
+    fn add_parsed_asset(
+        &mut self,
+        line_number: i32,
+        object_type: &str,
+        object_name: &str,
+        params: Vec<String>,
+    ) -> Result<(), NetworkGraphLoadError> {
+        if self.asset_index(object_name).is_some() {
+            return Err(NetworkGraphLoadError::ParseError(
+                line_number,
+                format!("Duplicate asset name: {object_name}"),
+            ));
+        }
+        self.add_asset(NetworkGraphAsset {
+            asset_type: NetworkGraphAssetType::from_str(object_type, params).map_err(|err| {
+                NetworkGraphLoadError::ObjectParseError(line_number, object_type.to_string(), err)
+            })?,
+            name: object_name.to_string(),
+        });
+        Ok(())
+    }
+}
+
+/// Asset paths of the files a level `include`s
+fn include_paths(source: &str) -> impl Iterator<Item = String> + '_ {
+    source
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("include "))
+        .map(|path| path.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::asset::LoadState;
+
+    #[test]
+    fn test_network_graph_asset_type() {
+        let asset_type = NetworkGraphAssetType::from_str("pc", vec![]).unwrap();
+        assert_eq!(asset_type.as_str(), "pc");
+    }
+
+    #[test]
+    fn test_network_graph_asset() {
+        let asset = NetworkGraphAsset {
+            asset_type: NetworkGraphAssetType::Pc(),
+            name: "My PC".to_string(),
+        };
+        assert_eq!(asset.name, "My PC");
+        assert_eq!(asset.asset_type.as_str(), "pc");
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let graph = NetworkGraph::parse("type pc l01\ntype router r01\nlink l01 r01").unwrap();
+        assert_eq!(
+            graph.to_dot(),
+            "graph network {\n    \"l01\" [type=pc];\n    \"r01\" [type=router];\n    \"l01\" -- \"r01\";\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_command_rules() {
+        let graph = NetworkGraph::parse("allow ? ls note\ndeny note").unwrap();
+        assert!(graph.command_enabled("?"));
+        assert!(graph.command_enabled("ls"));
+        assert!(!graph.command_enabled("note"));
+        assert!(!graph.command_enabled("notes"));
+
+        // No rules means everything goes
+        let graph = NetworkGraph::parse("type pc l01").unwrap();
+        assert!(graph.command_enabled("notes"));
+    }
+
+    #[test]
+    fn test_version() {
+        assert!(NetworkGraph::parse("# A comment\nversion 1\ntype pc l01").is_ok());
+        assert!(matches!(
+            NetworkGraph::parse("version 99\ntype pc l01"),
+            Err(NetworkGraphLoadError::UnsupportedVersion(1, 99))
+        ));
+        // Only the first directive can say what version the file is
+        assert!(matches!(
+            NetworkGraph::parse("type pc l01\nversion 1"),
+            Err(NetworkGraphLoadError::ParseError(2, _))
+        ));
+    }
+
+    #[test]
+    fn test_ambience() {
+        let graph = NetworkGraph::parse(
+            "ambience audio/ambience/server_room_hum.ogg\nambience audio/ambience/rain on glass.ogg",
+        )
+        .unwrap();
+        assert_eq!(
+            graph.ambience_paths,
+            vec![
+                "audio/ambience/server_room_hum.ogg",
+                "audio/ambience/rain on glass.ogg"
+            ]
+        );
+        // Only the loader turns the paths into audio
+        assert!(graph.ambience.is_empty());
+
+        assert!(matches!(
+            NetworkGraph::parse("type pc l01\nambience"),
+            Err(NetworkGraphLoadError::ParseError(2, _))
+        ));
+    }
+
+    #[test]
+    fn test_templates() {
+        let graph = NetworkGraph::parse(
+            "template office_pc: pc os=linux user=guest\n\
+             node ws01 from office_pc\n\
+             node ws02 from office_pc user=admin\n\
+             type router r01\n\
+             link ws01 r01",
+        )
+        .unwrap();
+        assert_eq!(graph.asset_index("ws02"), Some(1));
+        assert_eq!(graph.assets[1].asset_type.as_str(), "pc");
+        assert_eq!(graph.links, vec![(0, 2)]);
+
+        assert!(matches!(
+            NetworkGraph::parse("node ws01 from nothing"),
+            Err(NetworkGraphLoadError::ParseError(1, _))
+        ));
+        assert!(matches!(
+            NetworkGraph::parse("template toaster: toaster\nnode t01 from toaster"),
+            Err(NetworkGraphLoadError::ObjectParseError(2, ..))
+        ));
+    }
+
+    #[test]
+    fn test_include() {
+        let includes = HashMap::from([
+            (
+                "levels/office.txt".to_string(),
+                Ok("type router r01\ninclude levels/pcs.txt".to_string()),
+            ),
+            (
+                "levels/pcs.txt".to_string(),
+                Ok("version 1\ntype pc l01".to_string()),
+            ),
+            (
+                "levels/loop.txt".to_string(),
+                Ok("include levels/loop.txt".to_string()),
+            ),
+        ]);
+
+        let graph =
+            NetworkGraph::parse_with_includes("include levels/office.txt\nlink l01 r01", &includes)
+                .unwrap();
+        assert_eq!(graph.asset_index("r01"), Some(0));
+        assert_eq!(graph.asset_index("l01"), Some(1));
+        assert_eq!(graph.links, vec![(1, 0)]);
+
+        // Errors point at the line in this file that did the including
+        assert!(matches!(
+            NetworkGraph::parse_with_includes("type pc l01\ninclude levels/loop.txt", &includes),
+            Err(NetworkGraphLoadError::InIncludedFile(2, _, err))
+                if matches!(*err, NetworkGraphLoadError::IncludeError(1, ..))
+        ));
+        assert!(matches!(
+            NetworkGraph::parse("include levels/missing.txt"),
+            Err(NetworkGraphLoadError::IncludeError(1, ..))
+        ));
+    }
+
+    #[test]
+    fn test_parsing_network_graph() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<NetworkGraph>();
+        app.init_asset_loader::<NetworkGraph>();
+        let handle: Handle<NetworkGraph> = app
+            .world()
+            .resource::<AssetServer>()
+            .load("levels/test01.txt");
+        loop {
+            app.update();
+            if let LoadState::Loaded = app.world().resource::<AssetServer>().load_state(&handle) {
+                break;
+            }
+            if let LoadState::Failed(err) =
+                app.world().resource::<AssetServer>().load_state(&handle)
+            {
+                panic!("Failed to load asset: {:?} - {:?}", handle, err);
+            }
+        }
+
+        assert!(app.world().resource::<AssetServer>().is_loaded(&handle));
+        let graph = app
+            .world()
+            .resource::<Assets<NetworkGraph>>()
+            .get(&handle)
+            .unwrap();
+
+        assert_eq!(graph.assets.len(), 4);
+        assert_eq!(graph.assets[0].name, "l01");
+        assert_eq!(graph.assets[1].name, "l02");
+        assert_eq!(graph.assets[2].name, "l03");
+        assert_eq!(graph.assets[3].name, "r01");
+        assert_eq!(graph.links.len(), 3);
+        assert_eq!(graph.links[0], (0, 3)); // l01 -> r01
+        assert_eq!(graph.links[1], (1, 3)); // l02 -> r01
+        assert_eq!(graph.links[2], (2, 3)); // l03 -> r01
+
+        assert_eq!(graph.asset_index("r01"), Some(3));
+        assert_eq!(graph.neighbors(3), &[0, 1, 2]);
+        assert_eq!(graph.neighbors(0), &[3]);
+        assert_eq!(graph.path(0, 2), Some(vec![0, 3, 2]));
+        assert_eq!(graph.path(3, 3), Some(vec![3]));
+    }
+}
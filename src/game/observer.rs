@@ -0,0 +1,66 @@
+//! Observer mode: the level plays itself, with a scripted player working through the network
+//! while the human just watches the graph and the terminal. Handy for seeing how a level plays
+//! out without playing it.
+
+use bevy::prelude::*;
+
+use super::network::{NetworkGraph, NetworkGraphAssetType};
+use crate::launch::LaunchConfig;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ObserverMode>();
+    // `--observe` starts out watching
+    let observing = app
+        .world()
+        .get_resource::<LaunchConfig>()
+        .is_some_and(|config| config.observe);
+    app.insert_resource(ObserverMode(observing));
+}
+
+/// Whether the current run plays itself (picked from the main menu, or launched with
+/// `--observe`)
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct ObserverMode(pub bool);
+
+/// Run condition for when the player's only watching
+pub fn observing(observer_mode: Res<ObserverMode>) -> bool {
+    observer_mode.0
+}
+
+/// What the scripted player types: a look around, then a way to every machine on the network,
+/// using whichever commands the level allows
+pub fn observer_script(graph: &NetworkGraph) -> Vec<String> {
+    let mut script = vec!["ls".to_string()];
+    for asset in &graph.assets {
+        if matches!(asset.asset_type, NetworkGraphAssetType::Internet()) {
+            continue;
+        }
+        if graph.command_enabled("auto") {
+            script.push(format!("auto connect {}", asset.name));
+        } else if graph.command_enabled("ls") {
+            script.push(format!("ls {}", asset.name));
+        }
+        if graph.command_enabled("note") {
+            script.push(format!("note {} mapped", asset.name));
+        }
+    }
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observer_script() {
+        let graph = NetworkGraph::parse(
+            "type internet i01\ntype router r01\nlink i01 r01\nallow ls note notes",
+        )
+        .unwrap();
+        assert_eq!(
+            observer_script(&graph),
+            vec!["ls", "ls r01", "note r01 mapped"]
+        );
+    }
+}
@@ -0,0 +1,13 @@
+//! Sandbox mode: the level without the level's restrictions, for messing around.
+
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Sandbox>();
+    app.init_resource::<Sandbox>();
+}
+
+/// Whether the current run is a sandbox run (picked from the main menu).
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct Sandbox(pub bool);
@@ -0,0 +1,67 @@
+//! Gamepad rumble for key moments, scaled by the player's chosen intensity.
+
+use std::time::Duration;
+
+use bevy::{
+    input::gamepad::{GamepadRumbleIntensity, GamepadRumbleRequest},
+    prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<HapticsSettings>();
+    app.init_resource::<HapticsSettings>();
+    app.add_event::<HapticPulse>();
+
+    app.add_systems(Update, rumble_gamepads.run_if(on_event::<HapticPulse>));
+}
+
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct HapticsSettings {
+    /// Multiplier for every pulse, from `0.0` (off) to `1.0`.
+    pub intensity: f32,
+}
+
+impl Default for HapticsSettings {
+    fn default() -> Self {
+        Self { intensity: 1.0 }
+    }
+}
+
+/// Send this to rumble every connected gamepad.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct HapticPulse {
+    /// How hard to rumble, from `0.0` to `1.0`, before the player's intensity setting.
+    pub strength: f32,
+    pub duration: Duration,
+}
+
+impl HapticPulse {
+    /// A soft tick, e.g. when a command finishes.
+    pub const TICK: Self = Self {
+        strength: 0.3,
+        duration: Duration::from_millis(60),
+    };
+}
+
+fn rumble_gamepads(
+    mut pulses: EventReader<HapticPulse>,
+    settings: Res<HapticsSettings>,
+    gamepad_query: Query<Entity, With<Gamepad>>,
+    mut rumble_requests: EventWriter<GamepadRumbleRequest>,
+) {
+    for pulse in pulses.read() {
+        let strength = (pulse.strength * settings.intensity).clamp(0.0, 1.0);
+        if strength <= 0.0 {
+            continue;
+        }
+
+        for gamepad in &gamepad_query {
+            rumble_requests.write(GamepadRumbleRequest::Add {
+                gamepad,
+                duration: pulse.duration,
+                intensity: GamepadRumbleIntensity::weak_motor(strength),
+            });
+        }
+    }
+}
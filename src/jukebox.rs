@@ -0,0 +1,184 @@
+//! Gameplay music the player picks: the `music` terminal command and the settings menu choose
+//! a track, and the track's name pops up briefly when it changes.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::{
+    asset_tracking::LoadResource,
+    audio::{Caption, music},
+    screens::Screen,
+    theme::palette,
+};
+
+/// Track names, which are also their file names under `audio/music`
+pub const TRACKS: [&str; 2] = ["Fluffing A Duck", "Monkeys Spinning Monkeys"];
+
+const NOW_PLAYING_SECS: f32 = 3.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Jukebox>();
+    app.init_resource::<Jukebox>();
+    app.register_type::<JukeboxAssets>();
+    app.load_resource::<JukeboxAssets>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), play_current_track);
+    app.add_systems(
+        Update,
+        (
+            play_current_track.run_if(resource_changed::<Jukebox>),
+            hide_now_playing,
+        )
+            .run_if(in_state(Screen::Gameplay).and(resource_exists::<JukeboxAssets>)),
+    );
+}
+
+/// Which track is playing during gameplay (an index into [`TRACKS`]), if any
+#[derive(Resource, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource)]
+pub struct Jukebox(pub Option<usize>);
+
+impl Jukebox {
+    pub fn track_name(&self) -> Option<&'static str> {
+        self.0.and_then(|index| TRACKS.get(index).copied())
+    }
+
+    /// Skips to the next track, starting from the top if nothing's playing
+    pub fn next(&mut self) {
+        self.0 = Some(self.0.map_or(0, |index| (index + 1) % TRACKS.len()));
+    }
+
+    /// Steps through the tracks and then "off", for cycling through them in settings
+    pub fn cycle(&mut self) {
+        self.0 = match self.0 {
+            None => Some(0),
+            Some(index) if index + 1 < TRACKS.len() => Some(index + 1),
+            Some(_) => None,
+        };
+    }
+
+    /// Plays the track with the given number (from 1) or name (any case, or just the start of
+    /// it). Returns whether there was one.
+    pub fn play(&mut self, track: &str) -> bool {
+        let track = track.to_lowercase();
+        let index = track
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| number.checked_sub(1))
+            .filter(|&index| index < TRACKS.len())
+            .or_else(|| {
+                TRACKS
+                    .iter()
+                    .position(|name| name.to_lowercase().starts_with(&track))
+            });
+        if index.is_some() {
+            self.0 = index;
+        }
+        index.is_some()
+    }
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct JukeboxAssets {
+    #[dependency]
+    tracks: Vec<Handle<AudioSource>>,
+}
+
+impl FromWorld for JukeboxAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            tracks: TRACKS
+                .iter()
+                .map(|name| assets.load(format!("audio/music/{name}.ogg")))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct JukeboxMusic;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct NowPlaying(Timer);
+
+fn play_current_track(
+    mut commands: Commands,
+    jukebox: Res<Jukebox>,
+    jukebox_assets: Option<Res<JukeboxAssets>>,
+    playing_query: Query<Entity, Or<(With<JukeboxMusic>, With<NowPlaying>)>>,
+) {
+    for entity in &playing_query {
+        commands.entity(entity).despawn();
+    }
+    let (Some(jukebox_assets), Some(index), Some(name)) =
+        (jukebox_assets, jukebox.0, jukebox.track_name())
+    else {
+        return;
+    };
+
+    commands.spawn((
+        Name::new("Jukebox Music"),
+        StateScoped(Screen::Gameplay),
+        JukeboxMusic,
+        music(jukebox_assets.tracks[index].clone()),
+        Caption(format!("music: {name}")),
+    ));
+    commands.spawn((
+        Name::new("Now Playing"),
+        StateScoped(Screen::Gameplay),
+        NowPlaying(Timer::from_seconds(NOW_PLAYING_SECS, TimerMode::Once)),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Px(10.0),
+            right: Px(10.0),
+            padding: UiRect::axes(Px(10.0), Px(5.0)),
+            ..default()
+        },
+        BackgroundColor(palette::BUTTON_BACKGROUND),
+        GlobalZIndex(1),
+        Pickable::IGNORE,
+        children![(
+            Text::new(format!("Now playing: {name}")),
+            TextColor(palette::BUTTON_TEXT),
+            Pickable::IGNORE,
+        )],
+    ));
+}
+
+fn hide_now_playing(
+    mut commands: Commands,
+    mut now_playing_query: Query<(Entity, &mut NowPlaying)>,
+    time: Res<Time>,
+) {
+    for (entity, mut now_playing) in &mut now_playing_query {
+        if now_playing.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_play_by_number_or_name() {
+        let mut jukebox = Jukebox::default();
+        assert!(jukebox.play("2"));
+        assert_eq!(jukebox.track_name(), Some("Monkeys Spinning Monkeys"));
+        assert!(jukebox.play("fluff"));
+        assert_eq!(jukebox.0, Some(0));
+        assert!(!jukebox.play("3"));
+        assert!(!jukebox.play("polka"));
+        assert_eq!(jukebox.0, Some(0));
+
+        jukebox.cycle();
+        jukebox.cycle();
+        assert_eq!(jukebox.0, None);
+        jukebox.next();
+        assert_eq!(jukebox.0, Some(0));
+    }
+}
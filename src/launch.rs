@@ -0,0 +1,159 @@
+//! Launch options, for linking testers and streamers straight to a level: command-line flags
+//! natively (`--level 03 --seed 42 --mute --observe`), and URL query parameters on the web
+//! (`?level=03&seed=42&mute=1&observe=1`).
+
+use bevy::{audio::Volume, prelude::*};
+use rand::{SeedableRng, rngs::StdRng};
+
+/// Has to be added before the plugins that read the launch options while they're being built
+pub(super) fn plugin(app: &mut App) {
+    let config = LaunchConfig::from_environment();
+    if config.mute {
+        app.insert_resource(GlobalVolume {
+            volume: Volume::SILENT,
+        });
+    }
+    app.insert_resource(GameRng(match config.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }));
+    app.insert_resource(config);
+}
+
+/// How the game was launched
+#[derive(Resource, Debug, Default, PartialEq)]
+pub struct LaunchConfig {
+    /// The level to start in, skipping the menus: a level number (`03` for
+    /// `levels/level_03.txt`) or a file name in `assets/levels`
+    pub level: Option<String>,
+    /// Seeds [`GameRng`], so runs can be repeated
+    pub seed: Option<u64>,
+    /// Start with the volume all the way down
+    pub mute: bool,
+    /// Watch the level play itself (observer mode)
+    pub observe: bool,
+    /// Check the game's files, print the report and exit
+    pub selftest: bool,
+}
+
+impl LaunchConfig {
+    /// Reads the options from the command line, or from the page URL on the web
+    fn from_environment() -> Self {
+        #[cfg(not(target_family = "wasm"))]
+        let options = flag_options(std::env::args().skip(1));
+        #[cfg(target_family = "wasm")]
+        let options = web_sys::window()
+            .and_then(|window| window.location().search().ok())
+            .map(|query| query_options(&query))
+            .unwrap_or_default();
+        Self::parse(options)
+    }
+
+    fn parse(options: impl IntoIterator<Item = (String, String)>) -> Self {
+        let mut config = Self::default();
+        for (key, value) in options {
+            match key.as_str() {
+                "level" => config.level = Some(value),
+                "seed" => match value.parse() {
+                    Ok(seed) => config.seed = Some(seed),
+                    Err(_) => warn!("Ignoring seed {value:?}, which isn't a whole number"),
+                },
+                "mute" => config.mute = is_on(&value),
+                "observe" => config.observe = is_on(&value),
+                "selftest" => config.selftest = is_on(&value),
+                // Other flags (`--trace-sim`...) are read where they're used
+                _ => {}
+            }
+        }
+        config
+    }
+
+    /// The asset path of the level to start in, if there is one
+    pub fn level_path(&self) -> Option<String> {
+        let level = self.level.as_ref()?;
+        Some(if level.contains('.') {
+            format!("levels/{level}")
+        } else {
+            format!("levels/level_{level}.txt")
+        })
+    }
+}
+
+// Bare flags (`--mute`, `?mute`) are on too
+fn is_on(value: &str) -> bool {
+    !matches!(value, "0" | "false")
+}
+
+/// Randomness for gameplay and effects, seeded from the launch options when they have a seed
+#[derive(Resource, Debug)]
+pub struct GameRng(pub StdRng);
+
+// `--key value`, `--key=value` and bare `--flag`s, as (key, value) pairs
+#[cfg(not(target_family = "wasm"))]
+fn flag_options(args: impl IntoIterator<Item = String>) -> Vec<(String, String)> {
+    let mut args = args.into_iter().peekable();
+    let mut options = Vec::new();
+    while let Some(arg) = args.next() {
+        let Some(flag) = arg.strip_prefix("--") else {
+            continue;
+        };
+        let option = match flag.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            // Only options that take a value take the next argument
+            None if matches!(flag, "level" | "seed") => {
+                (flag.to_string(), args.next().unwrap_or_default())
+            }
+            None => (flag.to_string(), String::new()),
+        };
+        options.push(option);
+    }
+    options
+}
+
+// `?key=value&flag` as (key, value) pairs
+#[cfg(any(target_family = "wasm", test))]
+fn query_options(query: &str) -> Vec<(String, String)> {
+    query
+        .trim_start_matches('?')
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key.to_string(), value.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_launch_options() {
+        let expected = LaunchConfig {
+            level: Some("03".to_string()),
+            seed: Some(42),
+            mute: true,
+            observe: false,
+            selftest: true,
+        };
+        let args = ["--selftest", "--level", "03", "--seed=42", "--mute"];
+        assert_eq!(
+            LaunchConfig::parse(flag_options(args.map(String::from))),
+            expected
+        );
+        assert_eq!(
+            LaunchConfig::parse(query_options("?level=03&seed=42&mute=1&selftest")),
+            expected
+        );
+
+        let config = LaunchConfig::parse(query_options("?seed=lots&mute=0&level=office.dot"));
+        assert_eq!(config.seed, None);
+        assert!(!config.mute);
+        assert_eq!(config.level_path().as_deref(), Some("levels/office.dot"));
+        assert_eq!(
+            expected.level_path().as_deref(),
+            Some("levels/level_03.txt")
+        );
+    }
+}
@@ -3,17 +3,35 @@
 // Disable console on Windows for non-dev builds.
 #![cfg_attr(not(feature = "dev"), windows_subsystem = "windows")]
 
+#[cfg(feature = "analytics")]
+mod analytics;
 mod asset_tracking;
 mod audio;
+mod crash;
 #[cfg(feature = "dev")]
 mod dev_tools;
+#[cfg(feature = "embedded_assets")]
+mod embedded_assets;
 mod game;
+mod haptics;
+mod jukebox;
+mod launch;
 mod menus;
+mod performance;
 mod screens;
+mod selftest;
 mod terminal;
 mod theme;
+mod zoom;
 
-use bevy::{asset::AssetMetaCheck, prelude::*};
+#[cfg(not(target_family = "wasm"))]
+use bevy::asset::io::AssetSourceBuilder;
+use bevy::{
+    asset::AssetMetaCheck,
+    log::{DEFAULT_FILTER, LogPlugin},
+    prelude::*,
+    window::ExitCondition,
+};
 
 fn main() -> AppExit {
     App::new().add_plugins(AppPlugin).run()
@@ -23,6 +41,25 @@ pub struct AppPlugin;
 
 impl Plugin for AppPlugin {
     fn build(&self, app: &mut App) {
+        // `--trace-sim` turns on the game's debug-level spans (commands, level loading...),
+        // which end up in the chrome trace when built with the `trace_sim` feature.
+        let trace_sim = std::env::args().any(|arg| arg == "--trace-sim");
+
+        // Players can drop their own key clicks into a `click_pack` folder next to the game.
+        // Asset sources have to be registered before the asset plugin is added.
+        #[cfg(not(target_family = "wasm"))]
+        app.register_asset_source(
+            terminal::CUSTOM_CLICKS_SOURCE,
+            AssetSourceBuilder::platform_default(terminal::CUSTOM_CLICKS_SOURCE, None),
+        );
+
+        // Single-file builds carry their essential assets inside, for when `assets/` is missing.
+        #[cfg(feature = "embedded_assets")]
+        app.register_asset_source(
+            bevy::asset::io::AssetSourceId::Default,
+            embedded_assets::asset_source(),
+        );
+
         // Add Bevy plugins.
         app.add_plugins(
             DefaultPlugins
@@ -40,19 +77,50 @@ impl Plugin for AppPlugin {
                         ..default()
                     }
                     .into(),
+                    // The terminal can be popped out into a second window, which shouldn't
+                    // keep the game running on its own.
+                    exit_condition: ExitCondition::OnPrimaryClosed,
+                    ..default()
+                })
+                .set(LogPlugin {
+                    filter: if trace_sim {
+                        format!("{DEFAULT_FILTER},bevy_jam_6=debug")
+                    } else {
+                        DEFAULT_FILTER.to_string()
+                    },
                     ..default()
                 }),
         );
 
+        // Release builds compile everything below warn level out (`release_max_level_warn` in
+        // Cargo.toml), spans included, so there's nothing for the flag to turn on.
+        #[cfg(not(debug_assertions))]
+        if trace_sim {
+            warn!(
+                "--trace-sim does nothing in release builds, which leave tracing out. Trace a debug build instead."
+            );
+        }
+
+        // Launch options go first, since other plugins set up from them.
+        app.add_plugins(launch::plugin);
+
         // Add other plugins.
         app.add_plugins((
+            #[cfg(feature = "analytics")]
+            analytics::plugin,
             asset_tracking::plugin,
             audio::plugin,
+            crash::plugin,
             game::plugin,
+            haptics::plugin,
+            jukebox::plugin,
             #[cfg(feature = "dev")]
             dev_tools::plugin,
             menus::plugin,
+            performance::plugin,
+            zoom::plugin,
             screens::plugin,
+            selftest::plugin,
             terminal::plugin,
             theme::plugin,
         ));
@@ -2,7 +2,13 @@
 
 use bevy::prelude::*;
 
-use crate::{asset_tracking::ResourceHandles, menus::Menu, screens::Screen, theme::widget};
+use crate::{
+    asset_tracking::ResourceHandles,
+    game::{ObserverMode, Sandbox},
+    menus::Menu,
+    screens::Screen,
+    theme::widget,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Main), spawn_main_menu);
@@ -15,22 +21,61 @@ fn spawn_main_menu(mut commands: Commands) {
         StateScoped(Menu::Main),
         #[cfg(not(target_family = "wasm"))]
         children![
-            widget::button("Play", enter_loading_or_gameplay_screen),
+            widget::button("Play", play),
+            widget::button("Sandbox", play_sandbox),
+            widget::button("Watch", watch),
             widget::button("Settings", open_settings_menu),
             widget::button("Credits", open_credits_menu),
             widget::button("Exit", exit_app),
         ],
         #[cfg(target_family = "wasm")]
         children![
-            widget::button("Play", enter_loading_or_gameplay_screen),
+            widget::button("Play", play),
+            widget::button("Sandbox", play_sandbox),
+            widget::button("Watch", watch),
             widget::button("Settings", open_settings_menu),
             widget::button("Credits", open_credits_menu),
         ],
     ));
 }
 
-fn enter_loading_or_gameplay_screen(
+fn play(
+    _: Trigger<Pointer<Click>>,
+    resource_handles: Res<ResourceHandles>,
+    mut sandbox: ResMut<Sandbox>,
+    mut observer_mode: ResMut<ObserverMode>,
+    next_screen: ResMut<NextState<Screen>>,
+) {
+    sandbox.0 = false;
+    observer_mode.0 = false;
+    enter_loading_or_gameplay_screen(resource_handles, next_screen);
+}
+
+fn play_sandbox(
+    _: Trigger<Pointer<Click>>,
+    resource_handles: Res<ResourceHandles>,
+    mut sandbox: ResMut<Sandbox>,
+    mut observer_mode: ResMut<ObserverMode>,
+    next_screen: ResMut<NextState<Screen>>,
+) {
+    sandbox.0 = true;
+    observer_mode.0 = false;
+    enter_loading_or_gameplay_screen(resource_handles, next_screen);
+}
+
+fn watch(
     _: Trigger<Pointer<Click>>,
+    resource_handles: Res<ResourceHandles>,
+    mut sandbox: ResMut<Sandbox>,
+    mut observer_mode: ResMut<ObserverMode>,
+    next_screen: ResMut<NextState<Screen>>,
+) {
+    sandbox.0 = false;
+    observer_mode.0 = true;
+    enter_loading_or_gameplay_screen(resource_handles, next_screen);
+}
+
+fn enter_loading_or_gameplay_screen(
     resource_handles: Res<ResourceHandles>,
     mut next_screen: ResMut<NextState<Screen>>,
 ) {
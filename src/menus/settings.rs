@@ -2,9 +2,23 @@
 //!
 //! Additional settings and accessibility options should go here.
 
-use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
+use bevy::{
+    audio::Volume, ecs::spawn::SpawnableList, input::common_conditions::input_just_pressed,
+    prelude::*, ui::Val::*,
+};
 
-use crate::{menus::Menu, screens::Screen, theme::prelude::*};
+use crate::{
+    audio::Captions,
+    game::PanelSplit,
+    haptics::HapticsSettings,
+    jukebox::Jukebox,
+    menus::Menu,
+    performance::PerformanceSettings,
+    screens::Screen,
+    terminal::{ClickPack, FamilyFriendly, KeyRepeat, TerminalSpeech, TerminalTimestamps},
+    theme::prelude::*,
+    zoom::{ZOOM_STEP, zoom},
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
@@ -18,6 +32,70 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         update_global_volume_label.run_if(in_state(Menu::Settings)),
     );
+
+    app.register_type::<MusicTrackLabel>();
+    app.add_systems(
+        Update,
+        update_music_track_label.run_if(in_state(Menu::Settings)),
+    );
+
+    app.register_type::<UiZoomLabel>();
+    app.add_systems(
+        Update,
+        update_ui_zoom_label.run_if(in_state(Menu::Settings)),
+    );
+
+    app.register_type::<GraphPanelLabel>();
+    app.add_systems(
+        Update,
+        update_graph_panel_label.run_if(in_state(Menu::Settings)),
+    );
+
+    app.register_type::<RumbleLabel>();
+    app.add_systems(Update, update_rumble_label.run_if(in_state(Menu::Settings)));
+
+    app.register_type::<TerminalSpeechLabel>();
+    app.add_systems(
+        Update,
+        update_terminal_speech_label.run_if(in_state(Menu::Settings)),
+    );
+
+    app.register_type::<CaptionsLabel>();
+    app.add_systems(
+        Update,
+        update_captions_label.run_if(in_state(Menu::Settings)),
+    );
+
+    app.register_type::<ClickPackLabel>();
+    app.add_systems(
+        Update,
+        update_click_pack_label.run_if(in_state(Menu::Settings)),
+    );
+
+    app.register_type::<KeyRepeatLabel>();
+    app.add_systems(
+        Update,
+        update_key_repeat_label.run_if(in_state(Menu::Settings)),
+    );
+
+    app.register_type::<TerminalTimestampsLabel>();
+    app.add_systems(
+        Update,
+        update_terminal_timestamps_label.run_if(in_state(Menu::Settings)),
+    );
+
+    app.register_type::<FamilyFriendlyLabel>();
+    app.add_systems(
+        Update,
+        update_family_friendly_label.run_if(in_state(Menu::Settings)),
+    );
+
+    app.register_type::<FpsCapLabel>();
+    app.register_type::<LowPowerLabel>();
+    app.add_systems(
+        Update,
+        update_performance_labels.run_if(in_state(Menu::Settings)),
+    );
 }
 
 fn spawn_settings_menu(mut commands: Commands) {
@@ -43,16 +121,42 @@ fn settings_grid() -> impl Bundle {
             grid_template_columns: RepeatedGridTrack::px(2, 400.0),
             ..default()
         },
-        children![
+        // Grouped, since spawn lists only go up to 12 at a time
+        Children::spawn((
             (
-                widget::label("Master Volume"),
-                Node {
-                    justify_self: JustifySelf::End,
-                    ..default()
-                }
+                settings_row("Master Volume", global_volume_widget()),
+                settings_row("Music", music_track_widget()),
+                settings_row("Terminal Speech", terminal_speech_widget()),
+                settings_row("Key Clicks", click_pack_widget()),
+                settings_row("Captions", captions_widget()),
             ),
-            global_volume_widget(),
-        ],
+            (
+                settings_row("UI Zoom", ui_zoom_widget()),
+                settings_row("Graph Panel", graph_panel_widget()),
+                settings_row("FPS Cap", fps_cap_widget()),
+                settings_row("Low Power Mode", low_power_widget()),
+            ),
+            (
+                settings_row("Key Repeat", key_repeat_widget()),
+                settings_row("Timestamps", terminal_timestamps_widget()),
+                settings_row("Family Friendly", family_friendly_widget()),
+                settings_row("Gamepad Rumble", rumble_widget()),
+            ),
+        )),
+    )
+}
+
+/// A label and its widget, filling one row of the settings grid
+fn settings_row(label: &'static str, setting: impl Bundle) -> impl SpawnableList<ChildOf> {
+    (
+        Spawn((
+            widget::label(label),
+            Node {
+                justify_self: JustifySelf::End,
+                ..default()
+            },
+        )),
+        Spawn(setting),
     )
 }
 
@@ -104,6 +208,471 @@ fn update_global_volume_label(
     label.0 = format!("{percent:3.0}%");
 }
 
+fn music_track_widget() -> impl Bundle {
+    (
+        Name::new("Music Track Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("~", cycle_music_track),
+            (
+                Name::new("Current Music Track"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), MusicTrackLabel)],
+            ),
+        ],
+    )
+}
+
+fn cycle_music_track(_: Trigger<Pointer<Click>>, mut jukebox: ResMut<Jukebox>) {
+    jukebox.cycle();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct MusicTrackLabel;
+
+fn update_music_track_label(
+    jukebox: Res<Jukebox>,
+    mut label: Single<&mut Text, With<MusicTrackLabel>>,
+) {
+    label.0 = jukebox.track_name().unwrap_or("Off").to_string();
+}
+
+fn ui_zoom_widget() -> impl Bundle {
+    (
+        Name::new("UI Zoom Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("-", zoom_out),
+            (
+                Name::new("Current UI Zoom"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), UiZoomLabel)],
+            ),
+            widget::button_small("+", zoom_in),
+        ],
+    )
+}
+
+fn zoom_out(_: Trigger<Pointer<Click>>, mut ui_scale: ResMut<UiScale>) {
+    zoom(&mut ui_scale, -ZOOM_STEP);
+}
+
+fn zoom_in(_: Trigger<Pointer<Click>>, mut ui_scale: ResMut<UiScale>) {
+    zoom(&mut ui_scale, ZOOM_STEP);
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct UiZoomLabel;
+
+fn update_ui_zoom_label(ui_scale: Res<UiScale>, mut label: Single<&mut Text, With<UiZoomLabel>>) {
+    let percent = 100.0 * ui_scale.0;
+    label.0 = format!("{percent:3.0}%");
+}
+
+fn graph_panel_widget() -> impl Bundle {
+    (
+        Name::new("Graph Panel Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("-", shrink_graph_panel),
+            (
+                Name::new("Current Graph Panel Size"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), GraphPanelLabel)],
+            ),
+            widget::button_small("+", grow_graph_panel),
+        ],
+    )
+}
+
+fn shrink_graph_panel(_: Trigger<Pointer<Click>>, mut split: ResMut<PanelSplit>) {
+    split.nudge(-0.1);
+}
+
+fn grow_graph_panel(_: Trigger<Pointer<Click>>, mut split: ResMut<PanelSplit>) {
+    split.nudge(0.1);
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct GraphPanelLabel;
+
+fn update_graph_panel_label(
+    split: Res<PanelSplit>,
+    mut label: Single<&mut Text, With<GraphPanelLabel>>,
+) {
+    let percent = 100.0 * split.graph_ratio;
+    label.0 = format!("{percent:3.0}%");
+}
+
+fn fps_cap_widget() -> impl Bundle {
+    (
+        Name::new("FPS Cap Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("-", lower_fps_cap),
+            (
+                Name::new("Current FPS Cap"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), FpsCapLabel)],
+            ),
+            widget::button_small("+", raise_fps_cap),
+        ],
+    )
+}
+
+fn low_power_widget() -> impl Bundle {
+    (
+        Name::new("Low Power Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("~", toggle_low_power),
+            (
+                Name::new("Current Low Power Mode"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), LowPowerLabel)],
+            ),
+        ],
+    )
+}
+
+fn lower_fps_cap(_: Trigger<Pointer<Click>>, mut settings: ResMut<PerformanceSettings>) {
+    settings.cycle_fps_cap(-1);
+}
+
+fn raise_fps_cap(_: Trigger<Pointer<Click>>, mut settings: ResMut<PerformanceSettings>) {
+    settings.cycle_fps_cap(1);
+}
+
+fn toggle_low_power(_: Trigger<Pointer<Click>>, mut settings: ResMut<PerformanceSettings>) {
+    settings.low_power = !settings.low_power;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct FpsCapLabel;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct LowPowerLabel;
+
+fn update_performance_labels(
+    settings: Res<PerformanceSettings>,
+    mut fps_cap_label: Single<&mut Text, (With<FpsCapLabel>, Without<LowPowerLabel>)>,
+    mut low_power_label: Single<&mut Text, (With<LowPowerLabel>, Without<FpsCapLabel>)>,
+) {
+    fps_cap_label.0 = match settings.fps_cap {
+        Some(fps) => format!("{fps}"),
+        None => "Off".to_string(),
+    };
+    low_power_label.0 = if settings.low_power { "On" } else { "Off" }.to_string();
+}
+
+fn terminal_speech_widget() -> impl Bundle {
+    (
+        Name::new("Terminal Speech Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("~", toggle_terminal_speech),
+            (
+                Name::new("Current Terminal Speech"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), TerminalSpeechLabel)],
+            ),
+        ],
+    )
+}
+
+fn toggle_terminal_speech(_: Trigger<Pointer<Click>>, mut speech: ResMut<TerminalSpeech>) {
+    speech.0 = !speech.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct TerminalSpeechLabel;
+
+fn update_terminal_speech_label(
+    speech: Res<TerminalSpeech>,
+    mut label: Single<&mut Text, With<TerminalSpeechLabel>>,
+) {
+    label.0 = if speech.0 { "On" } else { "Off" }.to_string();
+}
+
+fn captions_widget() -> impl Bundle {
+    (
+        Name::new("Captions Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("~", toggle_captions),
+            (
+                Name::new("Current Captions"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), CaptionsLabel)],
+            ),
+        ],
+    )
+}
+
+fn toggle_captions(_: Trigger<Pointer<Click>>, mut captions: ResMut<Captions>) {
+    captions.0 = !captions.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct CaptionsLabel;
+
+fn update_captions_label(
+    captions: Res<Captions>,
+    mut label: Single<&mut Text, With<CaptionsLabel>>,
+) {
+    label.0 = if captions.0 { "On" } else { "Off" }.to_string();
+}
+
+fn click_pack_widget() -> impl Bundle {
+    (
+        Name::new("Click Pack Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("~", cycle_click_pack),
+            (
+                Name::new("Current Click Pack"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), ClickPackLabel)],
+            ),
+        ],
+    )
+}
+
+fn cycle_click_pack(_: Trigger<Pointer<Click>>, mut pack: ResMut<ClickPack>) {
+    *pack = pack.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ClickPackLabel;
+
+fn update_click_pack_label(
+    pack: Res<ClickPack>,
+    mut label: Single<&mut Text, With<ClickPackLabel>>,
+) {
+    label.0 = pack.name().to_string();
+}
+
+fn key_repeat_widget() -> impl Bundle {
+    (
+        Name::new("Key Repeat Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("~", cycle_key_repeat),
+            (
+                Name::new("Current Key Repeat"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), KeyRepeatLabel)],
+            ),
+        ],
+    )
+}
+
+fn cycle_key_repeat(_: Trigger<Pointer<Click>>, mut key_repeat: ResMut<KeyRepeat>) {
+    *key_repeat = key_repeat.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct KeyRepeatLabel;
+
+fn update_key_repeat_label(
+    key_repeat: Res<KeyRepeat>,
+    mut label: Single<&mut Text, With<KeyRepeatLabel>>,
+) {
+    label.0 = key_repeat.name().to_string();
+}
+
+fn terminal_timestamps_widget() -> impl Bundle {
+    (
+        Name::new("Terminal Timestamps Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("~", toggle_terminal_timestamps),
+            (
+                Name::new("Current Terminal Timestamps"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), TerminalTimestampsLabel)],
+            ),
+        ],
+    )
+}
+
+fn toggle_terminal_timestamps(
+    _: Trigger<Pointer<Click>>,
+    mut timestamps: ResMut<TerminalTimestamps>,
+) {
+    timestamps.0 = !timestamps.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct TerminalTimestampsLabel;
+
+fn update_terminal_timestamps_label(
+    timestamps: Res<TerminalTimestamps>,
+    mut label: Single<&mut Text, With<TerminalTimestampsLabel>>,
+) {
+    label.0 = if timestamps.0 { "On" } else { "Off" }.to_string();
+}
+
+fn family_friendly_widget() -> impl Bundle {
+    (
+        Name::new("Family Friendly Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("~", toggle_family_friendly),
+            (
+                Name::new("Current Family Friendly"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), FamilyFriendlyLabel)],
+            ),
+        ],
+    )
+}
+
+fn toggle_family_friendly(_: Trigger<Pointer<Click>>, mut family_friendly: ResMut<FamilyFriendly>) {
+    family_friendly.0 = !family_friendly.0;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct FamilyFriendlyLabel;
+
+fn update_family_friendly_label(
+    family_friendly: Res<FamilyFriendly>,
+    mut label: Single<&mut Text, With<FamilyFriendlyLabel>>,
+) {
+    label.0 = if family_friendly.0 { "On" } else { "Off" }.to_string();
+}
+
+fn rumble_widget() -> impl Bundle {
+    (
+        Name::new("Rumble Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("-", lower_rumble),
+            (
+                Name::new("Current Rumble"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), RumbleLabel)],
+            ),
+            widget::button_small("+", raise_rumble),
+        ],
+    )
+}
+
+fn lower_rumble(_: Trigger<Pointer<Click>>, mut settings: ResMut<HapticsSettings>) {
+    settings.intensity = (settings.intensity - 0.25).max(0.0);
+}
+
+fn raise_rumble(_: Trigger<Pointer<Click>>, mut settings: ResMut<HapticsSettings>) {
+    settings.intensity = (settings.intensity + 0.25).min(1.0);
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct RumbleLabel;
+
+fn update_rumble_label(
+    settings: Res<HapticsSettings>,
+    mut label: Single<&mut Text, With<RumbleLabel>>,
+) {
+    let percent = 100.0 * settings.intensity;
+    label.0 = format!("{percent:3.0}%");
+}
+
 fn go_back_on_click(
     _: Trigger<Pointer<Click>>,
     screen: Res<State<Screen>>,
@@ -0,0 +1,53 @@
+//! The wiki menu, with every entry the player has unlocked so far.
+
+use bevy::{
+    ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+use crate::{menus::Menu, terminal::Wiki, theme::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Wiki), spawn_wiki_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Wiki).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_wiki_menu(mut commands: Commands, wiki: Res<Wiki>) {
+    let mut entries = wiki
+        .unlocked()
+        .map(|(topic, entry)| format!("{topic}: {entry}"))
+        .collect::<Vec<String>>();
+    if entries.is_empty() {
+        entries.push("Nothing in here yet. Go poke at the network.".to_string());
+    }
+
+    commands.spawn((
+        widget::ui_root("Wiki Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::Wiki),
+        children![
+            widget::header("Wiki"),
+            (
+                Name::new("Entries"),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Px(10.0),
+                    max_width: Px(800.0),
+                    ..default()
+                },
+                Children::spawn(SpawnIter(entries.into_iter().map(widget::label))),
+            ),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Pause);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Pause);
+}
@@ -0,0 +1,100 @@
+//! Frame-rate cap and low-power mode.
+//!
+//! The game spends most of its time waiting on the player to type, so there's no point
+//! rendering as fast as possible when nothing is happening.
+//!
+//! Natively the cap sleeps off the rest of each frame. Browsers can't be blocked, so there it
+//! has winit wait between updates instead: input still wakes the game early, but an idle tab
+//! runs at the capped rate.
+
+use std::time::Duration;
+
+use bevy::{
+    prelude::*,
+    winit::{UpdateMode, WinitSettings},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PerformanceSettings>();
+    app.init_resource::<PerformanceSettings>();
+
+    app.add_systems(
+        Update,
+        apply_update_mode.run_if(resource_changed::<PerformanceSettings>),
+    );
+
+    #[cfg(not(target_family = "wasm"))]
+    app.add_systems(Last, limit_frame_rate);
+}
+
+/// Frame-rate caps the player can cycle through in the settings menu. `None` means uncapped.
+pub const FPS_CAPS: [Option<u32>; 5] = [None, Some(30), Some(60), Some(120), Some(144)];
+
+/// How long to wait between updates in low-power mode when nothing is happening.
+const LOW_POWER_FOCUSED_WAIT: Duration = Duration::from_millis(100);
+const LOW_POWER_UNFOCUSED_WAIT: Duration = Duration::from_secs(1);
+
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct PerformanceSettings {
+    /// Maximum frames per second, or `None` for uncapped.
+    pub fps_cap: Option<u32>,
+    /// Only update on input (or every so often) instead of continuously.
+    pub low_power: bool,
+}
+
+impl PerformanceSettings {
+    /// Moves to the next (or previous, if `step` is negative) entry in [`FPS_CAPS`], wrapping around.
+    pub fn cycle_fps_cap(&mut self, step: isize) {
+        let current = FPS_CAPS
+            .iter()
+            .position(|cap| *cap == self.fps_cap)
+            .unwrap_or(0) as isize;
+        let next = (current + step).rem_euclid(FPS_CAPS.len() as isize) as usize;
+        self.fps_cap = FPS_CAPS[next];
+    }
+}
+
+fn apply_update_mode(settings: Res<PerformanceSettings>, mut winit: ResMut<WinitSettings>) {
+    *winit = if settings.low_power {
+        WinitSettings {
+            focused_mode: UpdateMode::reactive_low_power(LOW_POWER_FOCUSED_WAIT),
+            unfocused_mode: UpdateMode::reactive_low_power(LOW_POWER_UNFOCUSED_WAIT),
+        }
+    } else {
+        browser_frame_cap(settings.fps_cap).unwrap_or_else(WinitSettings::game)
+    };
+}
+
+/// In a browser, updates at most every frame of the cap unless there's input to handle
+#[cfg(target_family = "wasm")]
+fn browser_frame_cap(fps_cap: Option<u32>) -> Option<WinitSettings> {
+    let frame_time = Duration::from_secs_f64(1.0 / fps_cap? as f64);
+    Some(WinitSettings {
+        focused_mode: UpdateMode::reactive(frame_time),
+        unfocused_mode: UpdateMode::reactive(frame_time),
+    })
+}
+
+/// Native builds sleep in [`limit_frame_rate`] instead
+#[cfg(not(target_family = "wasm"))]
+fn browser_frame_cap(_fps_cap: Option<u32>) -> Option<WinitSettings> {
+    None
+}
+
+/// Sleeps off whatever is left of the frame budget when a cap is set.
+#[cfg(not(target_family = "wasm"))]
+fn limit_frame_rate(
+    settings: Res<PerformanceSettings>,
+    mut last_frame: Local<Option<std::time::Instant>>,
+) {
+    if let (Some(fps), Some(last_frame)) = (settings.fps_cap, *last_frame) {
+        let frame_time = Duration::from_secs_f64(1.0 / fps as f64);
+        let elapsed = last_frame.elapsed();
+        if elapsed < frame_time {
+            std::thread::sleep(frame_time - elapsed);
+        }
+    }
+
+    *last_frame = Some(std::time::Instant::now());
+}
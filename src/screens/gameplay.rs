@@ -2,7 +2,7 @@
 
 use bevy::{input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
 
-use crate::{Pause, game::spawn_level, menus::Menu, screens::Screen};
+use crate::{Pause, game::spawn_level, menus::Menu, screens::Screen, terminal};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Gameplay), spawn_level);
@@ -11,11 +11,18 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         (
-            (pause, spawn_pause_overlay, open_pause_menu).run_if(
-                in_state(Screen::Gameplay)
-                    .and(in_state(Menu::None))
-                    .and(input_just_pressed(KeyCode::KeyP).or(input_just_pressed(KeyCode::Escape))),
-            ),
+            (pause, spawn_pause_overlay, open_pause_menu)
+                // Keys typed into the terminal are the terminal's (its Esc lets go of the keyboard)
+                .run_if(
+                    in_state(Screen::Gameplay)
+                        .and(in_state(Menu::None))
+                        .and(not(terminal::terminal_focused))
+                        .and(
+                            input_just_pressed(KeyCode::KeyP)
+                                .or(input_just_pressed(KeyCode::Escape)),
+                        ),
+                )
+                .before(terminal::TerminalInput),
             close_menu.run_if(
                 in_state(Screen::Gameplay)
                     .and(not(in_state(Menu::None)))
@@ -23,6 +30,15 @@ pub(super) fn plugin(app: &mut App) {
             ),
         ),
     );
+    // Quick restart, for fast retries and speedruns
+    app.add_systems(
+        Update,
+        restart_level.run_if(
+            in_state(Screen::Gameplay)
+                .and(in_state(Menu::None))
+                .and(input_just_pressed(KeyCode::F5)),
+        ),
+    );
     app.add_systems(OnExit(Screen::Gameplay), (close_menu, unpause));
     app.add_systems(
         OnEnter(Menu::None),
@@ -34,6 +50,12 @@ fn unpause(mut next_pause: ResMut<NextState<Pause>>) {
     next_pause.set(Pause(false));
 }
 
+// Leaving gameplay resets the level (the screen, terminal, notes, run clock...), and the
+// loading screen sends it straight back once everything's loaded
+fn restart_level(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Loading);
+}
+
 fn pause(mut next_pause: ResMut<NextState<Pause>>) {
     next_pause.set(Pause(true));
 }
@@ -7,9 +7,17 @@ mod title;
 
 use bevy::prelude::*;
 
+use crate::launch::LaunchConfig;
+
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Screen>();
 
+    // Launching into a level (or to watch one) skips the splash and title screens
+    app.add_systems(
+        Startup,
+        skip_to_level.run_if(|config: Res<LaunchConfig>| config.level.is_some() || config.observe),
+    );
+
     app.add_plugins((
         gameplay::plugin,
         loading::plugin,
@@ -28,3 +36,7 @@ pub enum Screen {
     Loading,
     Gameplay,
 }
+
+fn skip_to_level(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Loading);
+}
@@ -1,14 +1,39 @@
 //! The title screen that appears after the splash screen.
 
-use bevy::prelude::*;
+use bevy::{input::keyboard::KeyboardInput, prelude::*};
 
-use crate::{menus::Menu, screens::Screen};
+use crate::{
+    menus::Menu,
+    screens::Screen,
+    terminal::{TerminalAssets, autotype_terminal},
+    theme::widget,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Title), open_main_menu);
     app.add_systems(OnExit(Screen::Title), close_menu);
+
+    // Play the attract sequence when nobody's touched anything for a while.
+    app.register_type::<AttractMode>();
+    app.add_systems(OnEnter(Screen::Title), reset_idle_timer);
+    app.add_systems(
+        Update,
+        (stop_attract_mode_on_input, start_attract_mode_when_idle)
+            .chain()
+            .run_if(in_state(Screen::Title)),
+    );
 }
 
+const IDLE_SECS_BEFORE_ATTRACT: f32 = 60.0;
+
+const ATTRACT_SCRIPT: &[&str] = &[
+    "?",
+    "ls",
+    "ls r01",
+    "note r01 routes everything. start there",
+    "notes",
+];
+
 fn open_main_menu(mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Main);
 }
@@ -16,3 +41,62 @@ fn open_main_menu(mut next_menu: ResMut<NextState<Menu>>) {
 fn close_menu(mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::None);
 }
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct AttractMode;
+
+/// How long the title screen has gone without input.
+#[derive(Resource, Debug)]
+struct IdleTimer(Timer);
+
+fn reset_idle_timer(mut commands: Commands) {
+    commands.insert_resource(IdleTimer(Timer::from_seconds(
+        IDLE_SECS_BEFORE_ATTRACT,
+        TimerMode::Once,
+    )));
+}
+
+fn stop_attract_mode_on_input(
+    mut commands: Commands,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut cursor_events: EventReader<CursorMoved>,
+    mut idle_timer: ResMut<IdleTimer>,
+    attract_query: Query<Entity, With<AttractMode>>,
+) {
+    let any_input = keyboard_events.read().count() > 0
+        || cursor_events.read().count() > 0
+        || mouse_input.get_just_pressed().next().is_some();
+    if !any_input {
+        return;
+    }
+
+    idle_timer.0.reset();
+    for entity in &attract_query {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn start_attract_mode_when_idle(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut idle_timer: ResMut<IdleTimer>,
+    terminal_assets: Option<Res<TerminalAssets>>,
+) {
+    if !idle_timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    let Some(terminal_assets) = terminal_assets else {
+        return;
+    };
+
+    commands.spawn((
+        widget::ui_root("Attract Mode"),
+        AttractMode,
+        BackgroundColor(Color::BLACK),
+        GlobalZIndex(3),
+        StateScoped(Screen::Title),
+        children![autotype_terminal(&terminal_assets, ATTRACT_SCRIPT)],
+    ));
+}
@@ -0,0 +1,163 @@
+//! A self-test of the game's own files, for checking a build before it's uploaded: every
+//! tracked asset loads, every bundled level parses, and every command has help text.
+//!
+//! Run it with the `selftest` terminal command, or launch with `--selftest` to print the
+//! report and exit (with a failing exit code if anything's wrong).
+
+use bevy::{
+    asset::{LoadState, RecursiveDependencyLoadState},
+    prelude::*,
+};
+
+use crate::{
+    asset_tracking::ResourceHandles,
+    game::{BUNDLED_LEVELS, NetworkGraph},
+    launch::LaunchConfig,
+    terminal,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<SelfTest>();
+    app.add_systems(Update, check_self_test.run_if(self_test_running));
+
+    let launched_for_self_test = app
+        .world()
+        .get_resource::<LaunchConfig>()
+        .is_some_and(|config| config.selftest);
+    if launched_for_self_test {
+        app.add_systems(Startup, |mut self_test: ResMut<SelfTest>| self_test.start());
+        app.add_systems(Update, exit_with_report.after(check_self_test));
+    }
+}
+
+/// A self-test in progress, or its results
+#[derive(Resource, Debug, Default)]
+pub struct SelfTest {
+    running: bool,
+    // The bundled levels, loaded fresh for checking
+    levels: Vec<Handle<NetworkGraph>>,
+    /// The finished report, until it's shown
+    pub report: Option<SelfTestReport>,
+}
+
+impl SelfTest {
+    pub fn start(&mut self) {
+        self.running = true;
+        self.report = None;
+    }
+}
+
+#[derive(Debug)]
+pub struct SelfTestReport {
+    pub lines: Vec<String>,
+    pub passed: bool,
+}
+
+impl SelfTestReport {
+    fn check(&mut self, passed: bool, line: String) {
+        self.lines
+            .push(format!("{} {line}", if passed { "PASS" } else { "FAIL" }));
+        self.passed &= passed;
+    }
+}
+
+fn self_test_running(self_test: Res<SelfTest>) -> bool {
+    self_test.running
+}
+
+fn check_self_test(
+    mut self_test: ResMut<SelfTest>,
+    resource_handles: Res<ResourceHandles>,
+    asset_server: Res<AssetServer>,
+    graphs: Res<Assets<NetworkGraph>>,
+) {
+    if self_test.levels.is_empty() {
+        self_test.levels = BUNDLED_LEVELS
+            .iter()
+            .map(|path| asset_server.load(*path))
+            .collect();
+    }
+
+    // Wait for everything to either load or fail
+    let tracked = resource_handles
+        .handles()
+        .map(|handle| asset_server.get_recursive_dependency_load_state(handle))
+        .collect::<Vec<_>>();
+    let levels = self_test
+        .levels
+        .iter()
+        .map(|handle| asset_server.get_load_state(handle))
+        .collect::<Vec<_>>();
+    if tracked.iter().any(|state| {
+        !matches!(
+            state,
+            Some(RecursiveDependencyLoadState::Loaded | RecursiveDependencyLoadState::Failed(_))
+        )
+    }) || levels
+        .iter()
+        .any(|state| !matches!(state, Some(LoadState::Loaded | LoadState::Failed(_))))
+    {
+        return;
+    }
+
+    let mut report = SelfTestReport {
+        lines: Vec::new(),
+        passed: true,
+    };
+
+    let failed = tracked
+        .iter()
+        .filter(|state| matches!(state, Some(RecursiveDependencyLoadState::Failed(_))))
+        .count();
+    report.check(
+        failed == 0,
+        format!(
+            "{} of {} tracked assets loaded",
+            tracked.len() - failed,
+            tracked.len()
+        ),
+    );
+
+    for ((path, handle), state) in BUNDLED_LEVELS.iter().zip(&self_test.levels).zip(levels) {
+        match (state, graphs.get(handle)) {
+            (Some(LoadState::Failed(err)), _) => report.check(false, format!("{path}: {err}")),
+            (_, Some(graph)) if graph.assets.is_empty() => {
+                report.check(false, format!("{path}: no nodes"));
+            }
+            _ => report.check(true, format!("{path} parses")),
+        }
+    }
+
+    let missing_help = terminal::commands_without_help();
+    if missing_help.is_empty() {
+        report.check(true, "every command has help text".to_string());
+    } else {
+        report.check(
+            false,
+            format!("no help text for {}", missing_help.join(", ")),
+        );
+    }
+
+    report.lines.push(if report.passed {
+        "Self-test passed.".to_string()
+    } else {
+        "Self-test FAILED.".to_string()
+    });
+    self_test.running = false;
+    self_test.levels.clear();
+    self_test.report = Some(report);
+}
+
+fn exit_with_report(mut self_test: ResMut<SelfTest>, mut app_exit: EventWriter<AppExit>) {
+    let Some(report) = self_test.report.take() else {
+        return;
+    };
+    for line in &report.lines {
+        println!("{line}");
+    }
+    app_exit.write(if report.passed {
+        AppExit::Success
+    } else {
+        AppExit::error()
+    });
+}
@@ -0,0 +1,140 @@
+//! A terminal that types a script into itself, for the title screen's attract mode (and
+//! observer mode, where a level plays itself).
+
+use bevy::prelude::*;
+
+use super::{
+    CommandContext, FamilyFriendly, TerminalAssets, TerminalContainer, TerminalCursor,
+    TerminalHistory, TerminalText, command::ScratchResources, scroll_to_latest, submit_line,
+    terminal,
+};
+use crate::game::{LevelAssets, NetworkGraph};
+
+const TYPING_INTERVAL_SECS: f32 = 0.12;
+// Pause on a finished line before "pressing" Enter
+const ENTER_DELAY_TICKS: usize = 6;
+
+/// Types `script` into the terminal below it line by line, looping forever.
+#[derive(Component, Debug)]
+pub struct TerminalAutotype {
+    script: Vec<String>,
+    line: usize,
+    // Ticks spent on the current line so far
+    ticks: usize,
+    timer: Timer,
+    // What the script's commands have done so far, so `notes` can show the `note` before it.
+    // Whatever the demo scribbles down shouldn't end up in the player's notes.
+    resources: ScratchResources,
+}
+
+impl TerminalAutotype {
+    pub fn new(script: Vec<String>) -> Self {
+        Self {
+            script,
+            line: 0,
+            ticks: 0,
+            timer: Timer::from_seconds(TYPING_INTERVAL_SECS, TimerMode::Repeating),
+            resources: ScratchResources::default(),
+        }
+    }
+}
+
+/// Builds a terminal bundle that types (and runs) `script` by itself
+pub fn autotype_terminal(terminal_assets: &TerminalAssets, script: &[&str]) -> impl Bundle {
+    (
+        terminal(terminal_assets),
+        TerminalAutotype::new(script.iter().map(ToString::to_string).collect()),
+    )
+}
+
+fn autotype(
+    mut commands: Commands,
+    time: Res<Time>,
+    terminal_assets: Option<Res<TerminalAssets>>,
+    level_assets: Option<Res<LevelAssets>>,
+    graphs: Res<Assets<NetworkGraph>>,
+    family_friendly: Res<FamilyFriendly>,
+    text: Res<TerminalText>,
+    mut autotype_query: Query<(Entity, &mut TerminalAutotype)>,
+    children_query: Query<&Children>,
+    mut cursor_query: Query<&mut TerminalCursor>,
+    mut container_query: Query<(&ComputedNode, &mut ScrollPosition), With<TerminalContainer>>,
+    history_query: Query<Entity, With<TerminalHistory>>,
+) {
+    let Some(terminal_assets) = terminal_assets else {
+        return;
+    };
+    let graph = level_assets.and_then(|level_assets| graphs.get(&level_assets.graph));
+
+    for (root, mut autotype) in &mut autotype_query {
+        if !autotype.timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        let descendants = children_query
+            .iter_descendants(root)
+            .collect::<Vec<Entity>>();
+        let Some(&cursor_entity) = descendants.iter().find(|&&e| cursor_query.contains(e)) else {
+            continue;
+        };
+        let Some(&container_entity) = descendants.iter().find(|&&e| container_query.contains(e))
+        else {
+            continue;
+        };
+        let Some(&history_entity) = descendants.iter().find(|&&e| history_query.contains(e)) else {
+            continue;
+        };
+        let Ok(mut cursor) = cursor_query.get_mut(cursor_entity) else {
+            continue;
+        };
+        let Some(line) = autotype.script.get(autotype.line).cloned() else {
+            continue;
+        };
+
+        // Type the next character, or hit Enter once the line's done
+        autotype.ticks += 1;
+        let typed = cursor.current_input.chars().count();
+        if let Some(next) = line.chars().nth(typed) {
+            cursor.insert(&next.to_string());
+            continue;
+        }
+        if autotype.ticks < line.chars().count() + ENTER_DELAY_TICKS {
+            continue;
+        }
+
+        let input_raw = cursor.take_input();
+        let output = submit_line(
+            &mut commands,
+            history_entity,
+            &input_raw,
+            None,
+            // The demo talks the way the player's own terminal would
+            &mut CommandContext {
+                family_friendly: family_friendly.0,
+                text: &text,
+                ..autotype.resources.context(graph)
+            },
+            &terminal_assets,
+        );
+        if let Ok((container_node, mut container_scroll)) =
+            container_query.get_mut(container_entity)
+        {
+            scroll_to_latest(container_node, &mut container_scroll, output.lines.len());
+        }
+
+        // Start over (with a clean screen and a clean slate) after the last line
+        autotype.ticks = 0;
+        autotype.line += 1;
+        if autotype.line >= autotype.script.len() {
+            autotype.line = 0;
+            autotype.resources = ScratchResources::default();
+            commands
+                .entity(history_entity)
+                .despawn_related::<Children>();
+        }
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, autotype);
+}
@@ -0,0 +1,227 @@
+//! Integer arithmetic and base conversion for the `calc` and `conv` commands.
+//!
+//! Everything is a 64-bit signed integer: ports, offsets and addresses all fit, and
+//! bitwise operators behave the way hackers expect.
+
+use std::{iter::Peekable, net::Ipv4Addr, str::Chars};
+
+use thiserror::Error;
+
+/// Something wrong with what the player typed
+#[derive(Debug, Error, PartialEq)]
+pub enum CalcError {
+    #[error("Can't read {0} as a number")]
+    BadNumber(String),
+    #[error("Didn't expect {0:?} there")]
+    Unexpected(char),
+    #[error("Expression ends too early")]
+    UnexpectedEnd,
+    #[error("Division by zero. Nice try.")]
+    DivisionByZero,
+    #[error("Number too big, even for me")]
+    Overflow,
+}
+
+/// Reads an integer literal: decimal, `0x` hex, `0b` binary, or a dotted IPv4 address
+pub fn parse_number(input: &str) -> Result<i64, CalcError> {
+    let bad_number = || CalcError::BadNumber(input.to_string());
+    let lower = input.to_ascii_lowercase();
+    if let Some(hex) = lower.strip_prefix("0x") {
+        i64::from_str_radix(hex, 16).map_err(|_| bad_number())
+    } else if let Some(bin) = lower.strip_prefix("0b") {
+        i64::from_str_radix(bin, 2).map_err(|_| bad_number())
+    } else if lower.contains('.') {
+        lower
+            .parse::<Ipv4Addr>()
+            .map(|ip| u32::from(ip) as i64)
+            .map_err(|_| bad_number())
+    } else {
+        lower.parse::<i64>().map_err(|_| bad_number())
+    }
+}
+
+/// Formats a number in the given base (`hex`, `dec`, `bin` or `ip`)
+pub fn convert(value: i64, base: &str) -> Option<String> {
+    match base {
+        "hex" => Some(format!("{value:#x}")),
+        "dec" => Some(value.to_string()),
+        "bin" => Some(format!("{value:#b}")),
+        "ip" => u32::try_from(value)
+            .ok()
+            .map(|value| Ipv4Addr::from(value).to_string()),
+        _ => None,
+    }
+}
+
+/// Evaluates an integer expression with the usual arithmetic and bitwise operators
+pub fn evaluate(expression: &str) -> Result<i64, CalcError> {
+    let mut parser = Parser {
+        chars: expression.chars().peekable(),
+    };
+    let value = parser.or()?;
+    match parser.next_token() {
+        None => Ok(value),
+        Some(c) => Err(CalcError::Unexpected(c)),
+    }
+}
+
+// Recursive descent, one function per precedence level (loosest first)
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn peek_token(&mut self) -> Option<char> {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+        self.chars.peek().copied()
+    }
+
+    fn next_token(&mut self) -> Option<char> {
+        self.peek_token()?;
+        self.chars.next()
+    }
+
+    fn or(&mut self) -> Result<i64, CalcError> {
+        let mut value = self.xor()?;
+        while self.peek_token() == Some('|') {
+            self.chars.next();
+            value |= self.xor()?;
+        }
+        Ok(value)
+    }
+
+    fn xor(&mut self) -> Result<i64, CalcError> {
+        let mut value = self.and()?;
+        while self.peek_token() == Some('^') {
+            self.chars.next();
+            value ^= self.and()?;
+        }
+        Ok(value)
+    }
+
+    fn and(&mut self) -> Result<i64, CalcError> {
+        let mut value = self.shift()?;
+        while self.peek_token() == Some('&') {
+            self.chars.next();
+            value &= self.shift()?;
+        }
+        Ok(value)
+    }
+
+    fn shift(&mut self) -> Result<i64, CalcError> {
+        let mut value = self.sum()?;
+        while let Some(c @ ('<' | '>')) = self.peek_token() {
+            self.chars.next();
+            if self.chars.next() != Some(c) {
+                return Err(CalcError::Unexpected(c));
+            }
+            let amount = u32::try_from(self.sum()?).map_err(|_| CalcError::Overflow)?;
+            value = if c == '<' {
+                value.checked_shl(amount)
+            } else {
+                value.checked_shr(amount)
+            }
+            .ok_or(CalcError::Overflow)?;
+        }
+        Ok(value)
+    }
+
+    fn sum(&mut self) -> Result<i64, CalcError> {
+        let mut value = self.product()?;
+        while let Some(c @ ('+' | '-')) = self.peek_token() {
+            self.chars.next();
+            let rhs = self.product()?;
+            value = if c == '+' {
+                value.checked_add(rhs)
+            } else {
+                value.checked_sub(rhs)
+            }
+            .ok_or(CalcError::Overflow)?;
+        }
+        Ok(value)
+    }
+
+    fn product(&mut self) -> Result<i64, CalcError> {
+        let mut value = self.unary()?;
+        while let Some(c @ ('*' | '/' | '%')) = self.peek_token() {
+            self.chars.next();
+            let rhs = self.unary()?;
+            if c != '*' && rhs == 0 {
+                return Err(CalcError::DivisionByZero);
+            }
+            value = match c {
+                '*' => value.checked_mul(rhs),
+                '/' => value.checked_div(rhs),
+                _ => value.checked_rem(rhs),
+            }
+            .ok_or(CalcError::Overflow)?;
+        }
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> Result<i64, CalcError> {
+        match self.peek_token() {
+            Some('-') => {
+                self.chars.next();
+                self.unary()?.checked_neg().ok_or(CalcError::Overflow)
+            }
+            Some('~') => {
+                self.chars.next();
+                Ok(!self.unary()?)
+            }
+            _ => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> Result<i64, CalcError> {
+        match self.next_token() {
+            Some('(') => {
+                let value = self.or()?;
+                match self.next_token() {
+                    Some(')') => Ok(value),
+                    Some(c) => Err(CalcError::Unexpected(c)),
+                    None => Err(CalcError::UnexpectedEnd),
+                }
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let mut literal = c.to_string();
+                while let Some(c) = self
+                    .chars
+                    .next_if(|c| c.is_ascii_alphanumeric() || *c == '.')
+                {
+                    literal.push(c);
+                }
+                parse_number(&literal)
+            }
+            Some(c) => Err(CalcError::Unexpected(c)),
+            None => Err(CalcError::UnexpectedEnd),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate() {
+        assert_eq!(evaluate("1 + 2 * 3"), Ok(7));
+        assert_eq!(evaluate("(1 + 2) * 3"), Ok(9));
+        assert_eq!(evaluate("0xff & 0b1010"), Ok(10));
+        assert_eq!(evaluate("1 << 8 | 1"), Ok(257));
+        assert_eq!(evaluate("-~0"), Ok(1));
+        assert_eq!(evaluate("8080 % 1000"), Ok(80));
+        assert_eq!(evaluate("1 / 0"), Err(CalcError::DivisionByZero));
+        assert_eq!(evaluate("2 +"), Err(CalcError::UnexpectedEnd));
+        assert_eq!(evaluate("2 2"), Err(CalcError::Unexpected('2')));
+    }
+
+    #[test]
+    fn test_convert() {
+        let ip = parse_number("192.168.0.1").unwrap();
+        assert_eq!(convert(ip, "hex").as_deref(), Some("0xc0a80001"));
+        assert_eq!(convert(ip, "ip").as_deref(), Some("192.168.0.1"));
+        assert_eq!(convert(5, "bin").as_deref(), Some("0b101"));
+        assert_eq!(convert(-1, "ip"), None);
+    }
+}
@@ -0,0 +1,37 @@
+//! Captions for meaningful sounds, printed into the terminal history as they start playing
+//! (`[alarm rising]`), so nothing the game says only by sound is missed.
+
+use bevy::prelude::*;
+
+use super::{RunClock, TerminalAssets, TerminalHistory, TerminalTimestamps, history_entry};
+use crate::{
+    audio::{Caption, Captions},
+    screens::Screen,
+};
+
+fn print_captions(
+    mut commands: Commands,
+    captions: Res<Captions>,
+    caption_query: Query<&Caption, Added<Caption>>,
+    terminal_history: Single<Entity, With<TerminalHistory>>,
+    terminal_assets: Res<TerminalAssets>,
+    timestamps: Res<TerminalTimestamps>,
+    run_clock: Res<RunClock>,
+    time: Res<Time>,
+) {
+    if !captions.0 {
+        return;
+    }
+    for caption in &caption_query {
+        let timestamp = timestamps.0.then(|| run_clock.stamp(&time));
+        commands.entity(*terminal_history).with_child(history_entry(
+            &[format!("[{}]", caption.0)],
+            timestamp.as_deref(),
+            &terminal_assets,
+        ));
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, print_captions.run_if(in_state(Screen::Gameplay)));
+}
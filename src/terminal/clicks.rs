@@ -0,0 +1,141 @@
+//! Key click sounds for the terminal, from whichever pack the player picked. Native builds can
+//! also play a pack of their own, dropped into a `click_pack` folder next to the game.
+
+#[cfg(not(target_family = "wasm"))]
+use bevy::asset::LoadedFolder;
+use bevy::{input::ButtonState, prelude::*, window::PrimaryWindow};
+use rand::seq::SliceRandom;
+
+use super::{
+    TerminalAssets, TerminalState, TerminalWindow, key_repeat::TerminalKeys, terminal_focused,
+};
+use crate::{audio::sound_effect, launch::GameRng, screens::Screen};
+
+/// Asset source the custom pack's sounds are read from
+#[cfg(not(target_family = "wasm"))]
+pub const CUSTOM_CLICKS_SOURCE: &str = "click_pack";
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<ClickPack>();
+    app.init_resource::<ClickPack>();
+
+    #[cfg(not(target_family = "wasm"))]
+    {
+        app.init_resource::<CustomClicks>();
+        app.add_systems(
+            Update,
+            load_custom_clicks.run_if(resource_changed::<ClickPack>),
+        );
+    }
+
+    app.add_systems(
+        Update,
+        play_key_clicks.run_if(
+            in_state(Screen::Gameplay)
+                .and(in_state(TerminalState::Ready))
+                .and(terminal_focused),
+        ),
+    );
+}
+
+/// Which key clicks the terminal makes
+#[derive(Resource, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource)]
+pub enum ClickPack {
+    #[default]
+    Mechanical,
+    Silent,
+    /// Whatever .wav/.ogg files are in the `click_pack` folder
+    #[cfg(not(target_family = "wasm"))]
+    Custom,
+}
+
+impl ClickPack {
+    /// The next pack along, for cycling through them in settings
+    pub fn next(self) -> Self {
+        match self {
+            ClickPack::Mechanical => ClickPack::Silent,
+            #[cfg(not(target_family = "wasm"))]
+            ClickPack::Silent => ClickPack::Custom,
+            #[cfg(target_family = "wasm")]
+            ClickPack::Silent => ClickPack::Mechanical,
+            #[cfg(not(target_family = "wasm"))]
+            ClickPack::Custom => ClickPack::Mechanical,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ClickPack::Mechanical => "Mechanical",
+            ClickPack::Silent => "Silent",
+            #[cfg(not(target_family = "wasm"))]
+            ClickPack::Custom => "Custom",
+        }
+    }
+}
+
+/// The player's own pack, only loaded once it's picked
+#[cfg(not(target_family = "wasm"))]
+#[derive(Resource, Debug, Default)]
+struct CustomClicks(Option<Handle<LoadedFolder>>);
+
+#[cfg(not(target_family = "wasm"))]
+fn load_custom_clicks(
+    pack: Res<ClickPack>,
+    mut custom: ResMut<CustomClicks>,
+    asset_server: Res<AssetServer>,
+) {
+    if *pack == ClickPack::Custom && custom.0.is_none() {
+        custom.0 = Some(asset_server.load_folder(format!("{CUSTOM_CLICKS_SOURCE}://")));
+    }
+}
+
+fn play_key_clicks(
+    mut commands: Commands,
+    mut terminal_keys: TerminalKeys,
+    terminal_window: Res<TerminalWindow>,
+    primary_window_query: Query<Entity, With<PrimaryWindow>>,
+    pack: Res<ClickPack>,
+    terminal_assets: Res<TerminalAssets>,
+    mut rng: ResMut<GameRng>,
+    #[cfg(not(target_family = "wasm"))] custom: Res<CustomClicks>,
+    #[cfg(not(target_family = "wasm"))] folders: Res<Assets<LoadedFolder>>,
+) {
+    // Same keys the terminal takes: presses in its window
+    let input_window = terminal_window.0.or(primary_window_query.single().ok());
+    let presses = terminal_keys
+        .read()
+        .filter(|event| {
+            input_window.is_none_or(|window| window == event.window)
+                && event.state == ButtonState::Pressed
+        })
+        .count();
+    if presses == 0 {
+        return;
+    }
+
+    let clicks: Vec<Handle<AudioSource>> = match *pack {
+        ClickPack::Mechanical => terminal_assets.clicks.clone(),
+        ClickPack::Silent => Vec::new(),
+        // Anything in the folder that isn't audio is skipped
+        #[cfg(not(target_family = "wasm"))]
+        ClickPack::Custom => custom
+            .0
+            .as_ref()
+            .and_then(|folder| folders.get(folder))
+            .map(|folder| {
+                folder
+                    .handles
+                    .iter()
+                    .filter_map(|handle| handle.clone().try_typed::<AudioSource>().ok())
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+
+    for _ in 0..presses {
+        if let Some(click) = clicks.choose(&mut rng.0) {
+            commands.spawn(sound_effect(click.clone()));
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! The system clipboard, for pasting into the terminal and copying out of it.
+
+use bevy::prelude::*;
+
+/// Text on the system clipboard, if there is any
+#[cfg(not(target_family = "wasm"))]
+pub fn paste() -> Option<String> {
+    match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_text()) {
+        Ok(text) => Some(text),
+        // Nothing copied (or nothing that's text)
+        Err(arboard::Error::ContentNotAvailable) => None,
+        Err(err) => {
+            warn!("Couldn't read the clipboard: {err}");
+            None
+        }
+    }
+}
+
+/// Puts text on the system clipboard
+#[cfg(not(target_family = "wasm"))]
+pub fn copy(text: &str) {
+    if let Err(err) = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text)) {
+        warn!("Couldn't write to the clipboard: {err}");
+    }
+}
+
+/// Browsers only hand the clipboard over asynchronously (and with permission), so there's
+/// nothing to paste here
+#[cfg(target_family = "wasm")]
+pub fn paste() -> Option<String> {
+    warn_once!("Pasting into the terminal isn't supported in the browser yet");
+    None
+}
+
+#[cfg(target_family = "wasm")]
+pub fn copy(_text: &str) {
+    warn_once!("Copying from the terminal isn't supported in the browser yet");
+}
@@ -1,19 +1,433 @@
-const AVAILABLE_COMMANDS: [Command; 2] = [Command::Help, Command::List];
+use std::ops::Range;
+
+use bevy::{ecs::reflect::ReflectResource, log::debug_span, prelude::Resource, reflect::Reflect};
+use thiserror::Error;
+
+use super::{
+    calc, glob,
+    macros::Macros,
+    notes::Notes,
+    prompt::{PendingPrompt, Prompt, PromptAction, is_yes},
+    responses::{Response, TerminalText},
+    shell_prompt::TerminalPrompt,
+    timestamps::TerminalTimestamps,
+    tokenizer,
+    undo::{UndoAction, UndoHistory},
+    vi::TerminalEditMode,
+    wiki::Wiki,
+};
+use crate::{
+    game::{NetworkAnalysis, NetworkGraph, NetworkGraphAsset, NetworkGraphAssetType},
+    jukebox::{Jukebox, TRACKS},
+    selftest::SelfTest,
+};
+
+// So `repeat` can't be used to hang the game
+const MAX_REPEATS: usize = 20;
+
+// Tab stops in `echo` output are this many columns apart
+const TAB_WIDTH: usize = 8;
+
+// Where `export map` writes to when not told otherwise
+const DEFAULT_MAP_FILE: &str = "network_map.dot";
+
+const AVAILABLE_COMMANDS: [Command; 21] = [
+    Command::Help,
+    Command::List,
+    Command::Note,
+    Command::Notes,
+    Command::Auto,
+    Command::Macro,
+    Command::Undo,
+    Command::Calc,
+    Command::Conv,
+    Command::Timestamps,
+    Command::IfOk,
+    Command::Repeat,
+    Command::Export,
+    Command::Clear,
+    Command::Music,
+    Command::Set,
+    Command::SelfTest,
+    Command::Restart,
+    Command::Analyze,
+    Command::Echo,
+    Command::Wiki,
+];
+
+/// Names of the commands `help` has nothing to say about
+pub fn commands_without_help() -> Vec<&'static str> {
+    AVAILABLE_COMMANDS
+        .iter()
+        .filter(|command| !matches!(command, Command::Help) && command.help_text().is_none())
+        .filter_map(Command::name)
+        .collect()
+}
+
+/// Exit status of the last command that ran, as `$?` shows it (0 means it worked)
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct LastStatus(pub u8);
+
+/// Game state that commands can read and change
+pub struct CommandContext<'a> {
+    pub notes: &'a mut Notes,
+    pub macros: &'a mut Macros,
+    pub undo: &'a mut UndoHistory,
+    pub timestamps: &'a mut TerminalTimestamps,
+    pub last_status: &'a mut LastStatus,
+    pub prompt: &'a mut PendingPrompt,
+    pub jukebox: &'a mut Jukebox,
+    pub edit_mode: &'a mut TerminalEditMode,
+    pub self_test: &'a mut SelfTest,
+    // The level's network, if it's loaded
+    pub graph: Option<&'a NetworkGraph>,
+    // Sandbox runs ignore the level's command rules
+    pub sandbox: bool,
+    pub family_friendly: bool,
+    // The wording for responses and help
+    pub text: &'a TerminalText,
+    pub terminal_prompt: &'a mut TerminalPrompt,
+    pub wiki: &'a mut Wiki,
+}
+
+impl CommandContext<'_> {
+    /// Whether the current level lets the player use the named command
+    pub fn enabled(&self, name: &str) -> bool {
+        self.sandbox || self.graph.is_none_or(|graph| graph.command_enabled(name))
+    }
+
+    /// Names of the commands the player can use right now
+    pub fn available_commands(&self) -> impl Iterator<Item = &'static str> + '_ {
+        AVAILABLE_COMMANDS
+            .iter()
+            .filter_map(Command::name)
+            .filter(|name| self.enabled(name))
+    }
+
+    /// A canned response, in whichever voice the player picked
+    pub fn say(&self, response: Response) -> &str {
+        self.text.response(response, self.family_friendly)
+    }
+}
+
+/// Game state that isn't the player's, for commands that shouldn't touch theirs: the title screen
+/// and observer demos, and tests. The wording is the built-in one, so the demos swap in whatever
+/// [`TerminalText`] has loaded.
+#[derive(Debug, Default)]
+pub(super) struct ScratchResources {
+    pub notes: Notes,
+    pub macros: Macros,
+    pub undo: UndoHistory,
+    pub timestamps: TerminalTimestamps,
+    pub last_status: LastStatus,
+    pub prompt: PendingPrompt,
+    // Doesn't reach the music actually playing
+    pub jukebox: Jukebox,
+    pub edit_mode: TerminalEditMode,
+    pub self_test: SelfTest,
+    pub text: TerminalText,
+    pub terminal_prompt: TerminalPrompt,
+    // Nothing unlocked here counts as the player's
+    pub wiki: Wiki,
+}
+
+impl ScratchResources {
+    /// A context over these resources, on the given network (if any)
+    pub fn context<'a>(&'a mut self, graph: Option<&'a NetworkGraph>) -> CommandContext<'a> {
+        CommandContext {
+            notes: &mut self.notes,
+            macros: &mut self.macros,
+            undo: &mut self.undo,
+            timestamps: &mut self.timestamps,
+            last_status: &mut self.last_status,
+            prompt: &mut self.prompt,
+            jukebox: &mut self.jukebox,
+            edit_mode: &mut self.edit_mode,
+            self_test: &mut self.self_test,
+            graph,
+            sandbox: false,
+            family_friendly: false,
+            text: &self.text,
+            terminal_prompt: &mut self.terminal_prompt,
+            wiki: &mut self.wiki,
+        }
+    }
+}
+
+/// Something went wrong on our end (not the player's) while running a command.
+/// These get shown in the terminal instead of crashing the game.
+#[derive(Debug, Error)]
+pub enum CommandError {
+    #[error("Network graph is inconsistent: {0}")]
+    BrokenGraph(String),
+}
+
+/// What a command printed, and whether it worked
+#[derive(Debug)]
+pub struct CommandOutput {
+    pub lines: Vec<String>,
+    pub success: bool,
+    // Wipe the terminal before showing the lines
+    pub clear: bool,
+    // Start the level over once the line's done
+    pub restart: bool,
+}
+
+impl CommandOutput {
+    fn new() -> Self {
+        Self {
+            lines: Vec::new(),
+            success: true,
+            clear: false,
+            restart: false,
+        }
+    }
+
+    /// Adds the lines another command printed (dropping these if it cleared the screen)
+    fn append(&mut self, other: CommandOutput) {
+        if other.clear {
+            self.lines.clear();
+            self.clear = true;
+        }
+        self.restart |= other.restart;
+        self.lines.extend(other.lines);
+    }
+
+    fn push(&mut self, line: String) {
+        self.lines.push(line);
+    }
+
+    fn extend(&mut self, lines: impl IntoIterator<Item = String>) {
+        self.lines.extend(lines);
+    }
+
+    /// Prints a line and marks the command as failed
+    fn fail(&mut self, line: String) {
+        self.lines.push(line);
+        self.success = false;
+    }
+}
+
+/// How a command in a chain depends on the one before it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Chain {
+    // `;` (or the first command on the line)
+    Always,
+    // `&&`
+    OnSuccess,
+}
+
+/// Splits a line on `;` and `&&` into its commands (quoted or escaped ones don't count)
+pub fn split_chain(line: &str) -> Vec<(Chain, &str)> {
+    let mut segments = Vec::new();
+    let mut chain = Chain::Always;
+    let mut start = 0;
+    let mut bare = tokenizer::bare_chars(line).peekable();
+    while let Some((index, c)) = bare.next() {
+        let (next, len) = match c {
+            ';' => (Chain::Always, 1),
+            '&' if bare
+                .next_if(|&(following_index, following)| {
+                    following == '&' && following_index == index + 1
+                })
+                .is_some() =>
+            {
+                (Chain::OnSuccess, 2)
+            }
+            _ => continue,
+        };
+        segments.push((chain, &line[start..index]));
+        chain = next;
+        start = index + len;
+    }
+    segments.push((chain, &line[start..]));
+    segments
+}
+
+/// Runs a whole line of input, which may chain several commands with `;` and `&&`.
+/// Like a shell, the line succeeds if the last command that ran did.
+/// If a command asked a question, the line is its answer instead.
+pub fn run_line(line: &str, context: &mut CommandContext) -> Result<CommandOutput, CommandError> {
+    match context.prompt.0.take() {
+        Some(prompt) => Ok(answer_prompt(prompt, line, context)),
+        None => run_chain(line, context, true),
+    }
+}
+
+fn answer_prompt(prompt: Prompt, answer: &str, context: &mut CommandContext) -> CommandOutput {
+    let mut output = CommandOutput::new();
+    match prompt.action {
+        PromptAction::OverwriteMacro(name) => {
+            if is_yes(answer) {
+                output.push(format!("Recording {name}..."));
+                context.macros.recording = Some((name, Vec::new()));
+            } else {
+                output.fail(format!("Leaving {name} alone."));
+            }
+        }
+        PromptAction::OverwriteMap(path) => match context.graph {
+            Some(graph) if is_yes(answer) => export_map(graph, &path, &mut output),
+            _ => output.fail(format!("Leaving {path} alone.")),
+        },
+    }
+    context.last_status.0 = if output.success { 0 } else { 1 };
+    output
+}
+
+fn run_chain(
+    line: &str,
+    context: &mut CommandContext,
+    allow_macros: bool,
+) -> Result<CommandOutput, CommandError> {
+    let segments = split_chain(line);
+    let chained = segments.len() > 1;
+    let mut output = CommandOutput::new();
+    for (chain, segment) in segments {
+        if chain == Chain::OnSuccess && !output.success {
+            continue;
+        }
+
+        let segment = segment.replace("$?", &context.last_status.0.to_string());
+        let (command, words) = parse_words(&segment);
+        let mut args = words
+            .iter()
+            .map(|word| word.text.clone())
+            .collect::<Vec<String>>();
+        let _span = debug_span!("terminal_command", ?command, args = args.len()).entered();
+        // Stray separators ("ls;") shouldn't print blank lines
+        if chained && matches!(command, Command::Noop) {
+            continue;
+        }
+        // Macros can't play macros (or repeat things), so there's no way to loop forever
+        if !allow_macros && matches!(command, Command::Macro | Command::Repeat) {
+            output.fail(format!("{command}: not allowed in here"));
+            continue;
+        }
+
+        // Node arguments can use wildcards
+        if let (Command::List, Some(graph)) = (&command, context.graph) {
+            let names = graph.assets.iter().map(|asset| asset.name.as_str());
+            match glob::expand(&words, names) {
+                Ok(expanded) => args = expanded,
+                Err(err) => {
+                    output.fail(format!("{command}: {err}"));
+                    context.last_status.0 = 1;
+                    continue;
+                }
+            }
+        }
+
+        let result = command.run(&args, context)?;
+        output.success = result.success;
+        context.last_status.0 = if result.success { 0 } else { 1 };
+        output.append(result);
+
+        // The rest of the line can't be the answer, so it's dropped
+        if let Some(prompt) = &context.prompt.0 {
+            output.push(prompt.question.clone());
+            break;
+        }
+    }
+    Ok(output)
+}
+
+/// Whether a line should be recorded into a macro (macro commands themselves aren't)
+pub fn is_recordable(line: &str) -> bool {
+    !line.trim().is_empty()
+        && split_chain(line)
+            .into_iter()
+            .all(|(_, segment)| !matches!(parse_line(segment).0, Command::Macro))
+}
 
 /// Commands to be interpreted by the terminal
 ///
 /// When adding your own command, first add it here.
 /// Then, add the name of the command (from the terminal's point of view) to the `parse` function below.
-/// You'll also need to add your command to the `fmt::Display` implementation and the `AVAILABLE_COMMANDS` const so the "help" command can print it properly.
+/// You'll also need to add your command to the `name` function and the `AVAILABLE_COMMANDS` const so the "help" command can print it properly.
 /// Finally, add the logic for your command in the `run` command.
+/// If its arguments can be tab completed, say what they can be in `completions`.
 #[derive(Debug)]
 pub enum Command {
     List,
     Help,
+    Note,
+    Notes,
+    Auto,
+    Macro,
+    Undo,
+    Calc,
+    Conv,
+    Timestamps,
+    IfOk,
+    Repeat,
+    Export,
+    Clear,
+    Music,
+    Set,
+    SelfTest,
+    Restart,
+    Analyze,
+    Echo,
+    Wiki,
     Invalid, // When we can't recognize the command
     Noop,    // For when the user presses enter without any input
 }
 
+/// Byte ranges of the whitespace-separated words in a line
+pub fn words(line: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    line.split_whitespace().map(move |word| {
+        let start = word.as_ptr() as usize - line.as_ptr() as usize;
+        start..start + word.len()
+    })
+}
+
+/// A line the player ran, for playtest stats. Only the names of its commands are kept, never
+/// their arguments.
+#[cfg(feature = "analytics")]
+#[derive(bevy::prelude::Event, Debug)]
+pub struct CommandsRan {
+    pub names: Vec<&'static str>,
+    pub success: bool,
+}
+
+#[cfg(feature = "analytics")]
+impl CommandsRan {
+    pub fn new(line: &str, success: bool) -> Self {
+        let names = split_chain(line)
+            .into_iter()
+            .map(|(_, segment)| parse_line(segment).0)
+            .filter(|command| !matches!(command, Command::Noop))
+            .map(|command| command.name().unwrap_or("unknown"))
+            .collect();
+        Self { names, success }
+    }
+}
+
+/// Splits a line of input into its command and arguments
+pub fn parse_line(line: &str) -> (Command, Vec<String>) {
+    let (command, words) = parse_words(line);
+    (command, words.into_iter().map(|word| word.text).collect())
+}
+
+/// Like [`parse_line`], but keeping whether each argument was quoted
+fn parse_words(line: &str) -> (Command, Vec<tokenizer::Token>) {
+    let mut words = tokenizer::tokenize(line);
+
+    // Build command (or just do a noop if there is no meaningful input)
+    let command = match words.first() {
+        Some(name) => Command::parse(&name.text),
+        None => Command::Noop,
+    };
+
+    // Invalid commands keep their name around so it can be reported back
+    if !matches!(command, Command::Invalid | Command::Noop) {
+        words.remove(0);
+    }
+
+    (command, words)
+}
+
 impl Command {
     /// Parses the command from text input
     pub fn parse(input: &str) -> Command {
@@ -21,52 +435,985 @@ impl Command {
             "" => Command::Noop,
             "?" => Command::Help,
             "ls" => Command::List,
+            "note" => Command::Note,
+            "notes" => Command::Notes,
+            "auto" => Command::Auto,
+            "macro" => Command::Macro,
+            "undo" => Command::Undo,
+            "calc" => Command::Calc,
+            "conv" => Command::Conv,
+            "timestamps" => Command::Timestamps,
+            "if-ok" => Command::IfOk,
+            "repeat" => Command::Repeat,
+            "export" => Command::Export,
+            "clear" => Command::Clear,
+            "music" => Command::Music,
+            "set" => Command::Set,
+            "selftest" => Command::SelfTest,
+            "restart" => Command::Restart,
+            "analyze" => Command::Analyze,
+            "echo" => Command::Echo,
+            "wiki" => Command::Wiki,
             _ => Command::Invalid,
         }
     }
 
+    /// The name the terminal knows the command by, if it has one
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            Command::Help => Some("?"),
+            Command::List => Some("ls"),
+            Command::Note => Some("note"),
+            Command::Notes => Some("notes"),
+            Command::Auto => Some("auto"),
+            Command::Macro => Some("macro"),
+            Command::Undo => Some("undo"),
+            Command::Calc => Some("calc"),
+            Command::Conv => Some("conv"),
+            Command::Timestamps => Some("timestamps"),
+            Command::IfOk => Some("if-ok"),
+            Command::Repeat => Some("repeat"),
+            Command::Export => Some("export"),
+            Command::Clear => Some("clear"),
+            Command::Music => Some("music"),
+            Command::Set => Some("set"),
+            Command::SelfTest => Some("selftest"),
+            Command::Restart => Some("restart"),
+            Command::Analyze => Some("analyze"),
+            Command::Echo => Some("echo"),
+            Command::Wiki => Some("wiki"),
+            Command::Invalid | Command::Noop => None,
+        }
+    }
+
+    /// What `help <command>` says about it. `help` itself gets a canned response instead.
+    pub fn help_text(&self) -> Option<&'static str> {
+        match self {
+            Command::List => Some(
+                "List stuff. \"ls <node>\" for what's hooked up to it (wildcards like ws* work too).",
+            ),
+            Command::Note => Some("Jot something down. \"note -d <n>\" to scratch it out."),
+            Command::Notes => Some("Read back your scribbles."),
+            Command::Auto => Some(
+                "Let the script do the typing. \"auto connect <node>\" scans every hop on the way.",
+            ),
+            Command::Macro => Some(
+                "Teach it a routine. \"macro record <name>\", type away, \"macro stop\", then \"macro play <name>\".",
+            ),
+            Command::Undo => Some("Take back your last note or macro. Doesn't un-hack anything."),
+            Command::Calc => {
+                Some("Do the math. + - * / % & | ^ ~ << >>, with 0x and 0b numbers too.")
+            }
+            Command::Conv => Some("\"conv <value> hex|dec|bin|ip\". Takes numbers or dotted IPs."),
+            Command::Timestamps => {
+                Some("\"timestamps on|off\". Stamps every line with the time into the run.")
+            }
+            Command::IfOk => Some(
+                "\"if-ok <command>\" runs it only if the last one worked. $? has the last status.",
+            ),
+            Command::Repeat => Some("\"repeat <n> <command>\". Does what it says."),
+            Command::Export => {
+                Some("\"export map [file]\" saves the network as a Graphviz DOT file.")
+            }
+            Command::Clear => Some("Wipe the screen. Ctrl+L does it too."),
+            Command::Music => {
+                Some("\"music list|next|off\", or \"music play <track>\" by number or name.")
+            }
+            Command::Set => Some(
+                "\"set editmode vi|emacs\", or \"set prompt \\u@\\h\\$\" for a fancier prompt.",
+            ),
+            Command::SelfTest => {
+                Some("Checks the game's own files. Worth a run before uploading a build.")
+            }
+            Command::Restart => Some("Start the level over from scratch. F5 does it too."),
+            Command::Analyze => {
+                Some("Finds the choke points, and where the traffic goes, on this network.")
+            }
+            Command::Echo => Some(r"Says it back. \n starts a new line and \t lines things up."),
+            Command::Wiki => Some(
+                "\"wiki <topic>\" reads up on something you've run into. Just \"wiki\" lists them.",
+            ),
+            Command::Help | Command::Invalid | Command::Noop => None,
+        }
+    }
+
     // Command logic area
-    pub fn run(&self, args: &[String]) -> Vec<String> {
-        let mut output = Vec::new();
+    pub fn run(
+        &self,
+        args: &[String],
+        context: &mut CommandContext,
+    ) -> Result<CommandOutput, CommandError> {
+        let mut output = CommandOutput::new();
+
+        // The level decides which commands are available
+        if let Some(name) = self.name().filter(|name| !context.enabled(name)) {
+            output.fail(format!("{name}: command not available on this rig yet"));
+            return Ok(output);
+        }
 
         match self {
             Command::Help => {
-                if args.is_empty() {
-                    output.push("Lol, can't remember your own commands?".to_string());
-                    output.push(AVAILABLE_COMMANDS.map(|c| c.to_string()).join(" "));
-                } else {
+                if let Some(topic) = args.first() {
                     output.push(format!(
                         "{}: {}",
-                        args[0],
-                        match Command::parse(&args[0]) {
-                            Command::Help => "Uh... You serious?",
-                            Command::List => "List stuff. Like \"virus\" for viruses.",
-                            _ => "Man... I don't even know! What nonsense are you asking me?",
+                        topic,
+                        match Command::parse(topic) {
+                            Command::Help => context.say(Response::HelpOnHelp),
+                            command => context
+                                .text
+                                .help(&command)
+                                .unwrap_or_else(|| context.say(Response::HelpOnNonsense)),
                         }
                     ));
+                } else {
+                    output.push(context.say(Response::HelpForgotCommands).to_string());
+                    output.push(
+                        context
+                            .available_commands()
+                            .collect::<Vec<&str>>()
+                            .join(" "),
+                    );
                 }
             }
-            Command::Invalid => output.push(format!(
-                "Invalid command, dummy (type ? if you already forgot your own scripts): {}",
-                args[0]
+            Command::Invalid => output.fail(format!(
+                "{}: {}",
+                context.say(Response::InvalidCommand),
+                args.first().map_or("", String::as_str)
             )),
-            Command::List => output.push("TODO".to_string()),
+            Command::List => match (context.graph, args.first()) {
+                (None, _) => output.fail(context.say(Response::NothingToList).to_string()),
+                (Some(graph), None) => {
+                    output.extend(graph.assets.iter().map(describe));
+                    discover(context.wiki, graph, 0..graph.assets.len(), &mut output);
+                }
+                (Some(graph), Some(_)) => {
+                    for name in args {
+                        // Label each node's neighbors when there's more than one (e.g. "ls ws*")
+                        if args.len() > 1 {
+                            output.push(format!("{name}:"));
+                        }
+                        match graph.asset_index(name) {
+                            Some(index) => output.extend(list_neighbors(graph, index)?),
+                            None => output.fail(format!("Never heard of {name}.")),
+                        }
+                    }
+                    let shown = args
+                        .iter()
+                        .filter_map(|name| graph.asset_index(name))
+                        .flat_map(|index| graph.neighbors(index).iter().copied());
+                    discover(context.wiki, graph, shown, &mut output);
+                }
+            },
+            Command::Auto => match (context.graph, args) {
+                (None, _) => output.fail(context.say(Response::NowhereToGo).to_string()),
+                // It scans with `ls` at every hop, so it's only as available as `ls` is
+                (Some(_), [goal, _]) if goal == "connect" && !context.enabled("ls") => {
+                    output.fail("auto connect: needs ls, which isn't on this rig yet".to_string());
+                }
+                (Some(graph), [goal, target]) if goal == "connect" => {
+                    // Everything starts from the outside
+                    let entry = graph.assets.iter().position(|asset| {
+                        matches!(asset.asset_type, NetworkGraphAssetType::Internet())
+                    });
+                    let path = entry
+                        .zip(graph.asset_index(target))
+                        .and_then(|(entry, target)| graph.path(entry, target));
+                    let Some(path) = path else {
+                        output.fail(format!("Can't find a way to {target}."));
+                        return Ok(output);
+                    };
+
+                    // Run the scan the player would have typed at each hop
+                    for &hop in &path {
+                        let asset = graph.assets.get(hop).ok_or_else(|| {
+                            CommandError::BrokenGraph(format!(
+                                "path to {target} goes through missing asset #{hop}"
+                            ))
+                        })?;
+                        output.push(format!("> ls {}", asset.name));
+                        output.extend(list_neighbors(graph, hop)?);
+                    }
+                    output.push(format!("Made it to {target} in {} hops.", path.len() - 1));
+                    let shown = path
+                        .iter()
+                        .flat_map(|&hop| graph.neighbors(hop).iter().copied());
+                    discover(context.wiki, graph, shown, &mut output);
+                    context.terminal_prompt.host.clone_from(target);
+                }
+                _ => output.fail("Auto what? Usage: auto connect <node>".to_string()),
+            },
+            Command::Macro => match args {
+                [action, name] if action == "record" => {
+                    if let Some((recording, _)) = &context.macros.recording {
+                        output.fail(format!(
+                            "Already recording {recording}. \"macro stop\" first."
+                        ));
+                    } else if context.macros.saved.contains_key(name) {
+                        context.prompt.0 = Some(Prompt {
+                            question: format!("{name} already exists. Record over it? (y/n)"),
+                            masked: false,
+                            action: PromptAction::OverwriteMacro(name.clone()),
+                        });
+                    } else {
+                        context.macros.recording = Some((name.clone(), Vec::new()));
+                        output.push(format!("Recording {name}..."));
+                    }
+                }
+                [action] if action == "stop" => match context.macros.stop() {
+                    Some((name, previous)) => {
+                        output.push(format!("Saved macro {name}."));
+                        context.undo.push(UndoAction::MacroSaved { name, previous });
+                    }
+                    None => output.fail("Wasn't recording anything.".to_string()),
+                },
+                [action, name] if action == "play" => {
+                    let Some(lines) = context.macros.saved.get(name).cloned() else {
+                        output.fail(format!("No macro called {name}."));
+                        return Ok(output);
+                    };
+                    for line in lines {
+                        output.push(format!("> {line}"));
+                        let played = run_chain(&line, context, false)?;
+                        output.success &= played.success;
+                        output.append(played);
+                    }
+                }
+                [action] if action == "list" => {
+                    if context.macros.saved.is_empty() {
+                        output.push("No macros yet.".to_string());
+                    } else {
+                        let mut names = context
+                            .macros
+                            .saved
+                            .keys()
+                            .cloned()
+                            .collect::<Vec<String>>();
+                        names.sort();
+                        output.push(names.join(" "));
+                    }
+                }
+                _ => output.fail(
+                    "Usage: macro record <name> | macro stop | macro play <name> | macro list"
+                        .to_string(),
+                ),
+            },
+            Command::Note => match args {
+                [] => output.fail("Note what? Usage: note <text> | note -d <n>".to_string()),
+                [flag, index] if flag == "-d" => match index.parse::<usize>() {
+                    Ok(n) if (1..=context.notes.0.len()).contains(&n) => {
+                        let removed = context.notes.0.remove(n - 1);
+                        output.push(format!("Scratched out: {removed}"));
+                        context.undo.push(UndoAction::NoteDeleted {
+                            index: n - 1,
+                            text: removed,
+                        });
+                    }
+                    _ => output.fail(format!(
+                        "There's no note {index}. {}",
+                        context.say(Response::NoSuchNote)
+                    )),
+                },
+                // `-d` with no number (or too many) is a mistyped delete, not a note
+                [flag, ..] if flag == "-d" => {
+                    output.fail("Delete which? Usage: note -d <n>".to_string());
+                }
+                _ => {
+                    context.notes.0.push(args.join(" "));
+                    output.push(format!("Noted ({}).", context.notes.0.len()));
+                    context.undo.push(UndoAction::NoteAdded);
+                }
+            },
+            Command::Notes => {
+                if context.notes.0.is_empty() {
+                    output.push(context.say(Response::NoNotes).to_string());
+                } else {
+                    output.extend(
+                        context
+                            .notes
+                            .0
+                            .iter()
+                            .enumerate()
+                            .map(|(i, note)| format!("{}. {note}", i + 1)),
+                    );
+                }
+            }
+            Command::Undo => match context.undo.pop() {
+                None => output.fail("Nothing to undo.".to_string()),
+                Some(UndoAction::NoteAdded) => match context.notes.0.pop() {
+                    Some(note) => output.push(format!("Unwrote: {note}")),
+                    None => output.fail("That note's already gone.".to_string()),
+                },
+                Some(UndoAction::NoteDeleted { index, text }) => {
+                    let index = index.min(context.notes.0.len());
+                    output.push(format!("Unscratched: {text}"));
+                    context.notes.0.insert(index, text);
+                }
+                Some(UndoAction::MacroSaved { name, previous }) => match previous {
+                    Some(lines) => {
+                        context.macros.saved.insert(name.clone(), lines);
+                        output.push(format!("Put back the old {name}."));
+                    }
+                    None => {
+                        context.macros.saved.remove(&name);
+                        output.push(format!("Forgot macro {name}."));
+                    }
+                },
+            },
+            Command::Calc => match calc::evaluate(&args.join(" ")) {
+                Ok(value) => output.push(value.to_string()),
+                Err(err) => output.fail(format!("calc: {err}")),
+            },
+            Command::Conv => match args {
+                [value, base] => match calc::parse_number(value) {
+                    Ok(number) => match calc::convert(number, base) {
+                        Some(converted) => output.push(converted),
+                        None => output.fail(format!("conv: can't show {value} as {base}")),
+                    },
+                    Err(err) => output.fail(format!("conv: {err}")),
+                },
+                _ => output.fail("Usage: conv <value> hex|dec|bin|ip".to_string()),
+            },
+            Command::Timestamps => match args.first().map(String::as_str) {
+                Some("on") => {
+                    context.timestamps.0 = true;
+                    output.push("Timestamps on.".to_string());
+                }
+                Some("off") => {
+                    context.timestamps.0 = false;
+                    output.push("Timestamps off.".to_string());
+                }
+                _ => output.fail("Usage: timestamps on|off".to_string()),
+            },
+            // Neither runs macros, so they can't be used to build an endless loop
+            Command::IfOk => {
+                if args.is_empty() {
+                    output.fail("Usage: if-ok <command>".to_string());
+                } else if context.last_status.0 == 0 {
+                    output = run_chain(&tokenizer::join(args), context, false)?;
+                } else {
+                    // Stay failed, so a run of if-oks all get skipped
+                    output.success = false;
+                }
+            }
+            Command::Repeat => match args {
+                [count, command @ ..] if !command.is_empty() => match count.parse::<usize>() {
+                    Ok(n) if (1..=MAX_REPEATS).contains(&n) => {
+                        for _ in 0..n {
+                            let repeated = run_chain(&tokenizer::join(command), context, false)?;
+                            output.success &= repeated.success;
+                            output.append(repeated);
+                        }
+                    }
+                    _ => output.fail(format!("repeat: pick a count from 1 to {MAX_REPEATS}")),
+                },
+                _ => output.fail("Usage: repeat <n> <command>".to_string()),
+            },
+            Command::Export => match (context.graph, args) {
+                (None, _) => output.fail(context.say(Response::NothingToList).to_string()),
+                (Some(graph), [what, path @ ..]) if what == "map" && path.len() <= 1 => {
+                    let path = path.first().map_or(DEFAULT_MAP_FILE, String::as_str);
+                    // Browsers have no disk to clobber
+                    if cfg!(not(target_family = "wasm")) && std::path::Path::new(path).exists() {
+                        context.prompt.0 = Some(Prompt {
+                            question: format!("{path} already exists. Overwrite it? (y/n)"),
+                            masked: false,
+                            action: PromptAction::OverwriteMap(path.to_string()),
+                        });
+                    } else {
+                        export_map(graph, path, &mut output);
+                    }
+                }
+                _ => output.fail("Usage: export map [file]".to_string()),
+            },
+            Command::Clear => output.clear = true,
+            Command::Music => match args {
+                [] => output.push(match context.jukebox.track_name() {
+                    Some(name) => format!("Now playing: {name}"),
+                    None => "Nothing playing.".to_string(),
+                }),
+                [action] if action == "list" => {
+                    output.extend(TRACKS.iter().enumerate().map(|(index, name)| {
+                        let playing = if context.jukebox.0 == Some(index) {
+                            " <"
+                        } else {
+                            ""
+                        };
+                        format!("{}. {name}{playing}", index + 1)
+                    }))
+                }
+                [action] if action == "next" => {
+                    context.jukebox.next();
+                    output.push(format!(
+                        "Now playing: {}",
+                        context.jukebox.track_name().unwrap_or_default()
+                    ));
+                }
+                [action] if action == "off" => {
+                    context.jukebox.0 = None;
+                    output.push("Music off.".to_string());
+                }
+                [action, track @ ..] if action == "play" && !track.is_empty() => {
+                    if context.jukebox.play(&track.join(" ")) {
+                        output.push(format!(
+                            "Now playing: {}",
+                            context.jukebox.track_name().unwrap_or_default()
+                        ));
+                    } else {
+                        output.fail(format!("No track called {}.", track.join(" ")));
+                    }
+                }
+                _ => output.fail("Usage: music [list|next|off|play <track>]".to_string()),
+            },
+            Command::Set => match args {
+                [] => output.extend([
+                    format!("editmode {}", context.edit_mode.name()),
+                    format!("prompt {}", context.terminal_prompt.format),
+                ]),
+                [setting, mode] if setting == "editmode" => match TerminalEditMode::parse(mode) {
+                    Some(mode) => {
+                        *context.edit_mode = mode;
+                        output.push(format!("Editing the {} way.", mode.name()));
+                    }
+                    None => output.fail(format!("set: no {mode} edit mode (try vi or emacs)")),
+                },
+                [setting, format @ ..] if setting == "prompt" && !format.is_empty() => {
+                    context.terminal_prompt.format = format.join(" ");
+                    output.push(format!("Prompt's now {}", context.terminal_prompt.render()));
+                }
+                _ => output.fail("Usage: set [editmode vi|emacs | prompt <format>]".to_string()),
+            },
+            Command::SelfTest => {
+                // The assets take a moment, so the report shows up when they're done
+                context.self_test.start();
+                output.push("Self-test running...".to_string());
+            }
+            Command::Restart => {
+                output.restart = true;
+                output.push("Rebooting...".to_string());
+            }
+            Command::Analyze => match context.graph {
+                None => output.fail("Nothing to analyze. No network is loaded.".to_string()),
+                Some(graph) => output.extend(analyze(graph)?),
+            },
+            // The tokenizer has already turned `\n` and `\t` into the real thing
+            Command::Echo => output.extend(args.join(" ").split('\n').map(expand_tabs)),
+            Command::Wiki => match args {
+                [] => {
+                    let topics = context
+                        .wiki
+                        .unlocked()
+                        .map(|(topic, _)| topic)
+                        .collect::<Vec<&str>>();
+                    if topics.is_empty() {
+                        output.push("Nothing in here yet. Go poke at the network.".to_string());
+                    } else {
+                        output.push(topics.join(" "));
+                    }
+                }
+                [topic] => match context.wiki.entry(topic) {
+                    Some(entry) => output.push(format!("{topic}: {entry}")),
+                    None if context.wiki.has_entry(topic) => {
+                        output.fail(format!("The {topic} entry is still locked."));
+                    }
+                    None => output.fail(format!("Nothing written about {topic}.")),
+                },
+                _ => output.fail("Usage: wiki [topic]".to_string()),
+            },
             Command::Noop => output.push(String::new()),
         }
 
-        output
+        Ok(output)
+    }
+}
+
+impl Command {
+    /// What the next argument could be, given the ones before it, for tab completion
+    pub fn completions(&self, args: &[String], context: &CommandContext) -> Vec<String> {
+        let asset_names = || {
+            context.graph.map_or_else(Vec::new, |graph| {
+                graph
+                    .assets
+                    .iter()
+                    .map(|asset| asset.name.clone())
+                    .collect()
+            })
+        };
+        match (self, args) {
+            (Command::Help | Command::IfOk, []) | (Command::Repeat, [_]) => {
+                context.available_commands().map(String::from).collect()
+            }
+            // The command being run takes it from there
+            (Command::IfOk, [name, args @ ..]) | (Command::Repeat, [_, name, args @ ..]) => {
+                Command::parse(name).completions(args, context)
+            }
+            (Command::List, _) | (Command::Auto, [_]) => asset_names(),
+            (Command::Auto, []) => to_strings(&["connect"]),
+            (Command::Macro, []) => to_strings(&["record", "stop", "play", "list"]),
+            (Command::Macro, [action]) if action == "play" || action == "record" => {
+                let mut names = context
+                    .macros
+                    .saved
+                    .keys()
+                    .cloned()
+                    .collect::<Vec<String>>();
+                names.sort();
+                names
+            }
+            (Command::Conv, [_]) => to_strings(&["hex", "dec", "bin", "ip"]),
+            (Command::Timestamps, []) => to_strings(&["on", "off"]),
+            (Command::Export, []) => to_strings(&["map"]),
+            (Command::Music, []) => to_strings(&["list", "next", "off", "play"]),
+            (Command::Set, []) => to_strings(&["editmode", "prompt"]),
+            (Command::Set, [setting]) if setting == "editmode" => to_strings(&["emacs", "vi"]),
+            (Command::Wiki, []) => context
+                .wiki
+                .unlocked()
+                .map(|(topic, _)| topic.to_string())
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+// Tabs become spaces up to the next tab stop, since the terminal's text doesn't do tabs itself
+fn expand_tabs(line: &str) -> String {
+    let mut expanded = String::with_capacity(line.len());
+    let mut column = 0;
+    for c in line.chars() {
+        if c == '\t' {
+            let spaces = TAB_WIDTH - column % TAB_WIDTH;
+            expanded.extend(std::iter::repeat_n(' ', spaces));
+            column += spaces;
+        } else {
+            expanded.push(c);
+            column += 1;
+        }
+    }
+    expanded
+}
+
+fn to_strings(words: &[&str]) -> Vec<String> {
+    words.iter().map(|word| word.to_string()).collect()
+}
+
+/// Writes the network out as a DOT file
+#[cfg(not(target_family = "wasm"))]
+fn export_map(graph: &NetworkGraph, path: &str, output: &mut CommandOutput) {
+    match std::fs::write(path, graph.to_dot()) {
+        Ok(()) => output.push(format!("Map saved to {path}.")),
+        Err(err) => output.fail(format!("export: couldn't write {path}: {err}")),
+    }
+}
+
+/// Browsers don't have a file system to write to, so the DOT is printed for copying instead
+#[cfg(target_family = "wasm")]
+fn export_map(graph: &NetworkGraph, _path: &str, output: &mut CommandOutput) {
+    output.push("No disk in here. Copy this instead:".to_string());
+    output.extend(graph.to_dot().lines().map(str::to_string));
+}
+
+/// Unlocks the wiki entries for the kinds of asset just shown to the player, and says so
+fn discover(
+    wiki: &mut Wiki,
+    graph: &NetworkGraph,
+    shown: impl IntoIterator<Item = usize>,
+    output: &mut CommandOutput,
+) {
+    for index in shown {
+        let Some(asset) = graph.assets.get(index) else {
+            continue;
+        };
+        let topic = asset.asset_type.as_str();
+        if wiki.unlock(topic) {
+            output.push(format!(
+                "New wiki entry: {topic} (\"wiki {topic}\" to read it)"
+            ));
+        }
+    }
+}
+
+/// "name (type)" for every asset linked to the one at `index`
+fn list_neighbors(graph: &NetworkGraph, index: usize) -> Result<Vec<String>, CommandError> {
+    graph
+        .neighbors(index)
+        .iter()
+        .map(|&neighbor| {
+            let asset = graph.assets.get(neighbor).ok_or_else(|| {
+                CommandError::BrokenGraph(format!(
+                    "asset #{index} links to missing asset #{neighbor}"
+                ))
+            })?;
+            Ok(describe(asset))
+        })
+        .collect()
+}
+
+/// "name (type)", the way `ls` shows an asset
+fn describe(asset: &NetworkGraphAsset) -> String {
+    format!("{} ({})", asset.name, asset.asset_type.as_str())
+}
+
+// Names the assets in an analysis, busiest and most critical first
+fn analyze(graph: &NetworkGraph) -> Result<Vec<String>, CommandError> {
+    let analysis = NetworkAnalysis::of(graph);
+    let name = |index: usize| {
+        graph
+            .assets
+            .get(index)
+            .map(|asset| asset.name.as_str())
+            .ok_or_else(|| CommandError::BrokenGraph(format!("no asset #{index}")))
+    };
+
+    let mut lines = vec![match analysis.components.len() {
+        1 => format!("{} machines, all connected.", graph.assets.len()),
+        pieces => format!(
+            "{} machines, in {pieces} pieces that can't reach each other.",
+            graph.assets.len()
+        ),
+    }];
+    let chokes = analysis
+        .articulation_points
+        .iter()
+        .map(|&index| name(index))
+        .collect::<Result<Vec<&str>, CommandError>>()?;
+    lines.push(if chokes.is_empty() {
+        "No choke points: every machine has a way around.".to_string()
+    } else {
+        format!("Choke points: {}", chokes.join(" "))
+    });
+    // A handful is plenty, and keeps big networks readable
+    for index in analysis.busiest().into_iter().take(3) {
+        lines.push(format!(
+            "{} carries {:.0}% of routes",
+            name(index)?,
+            analysis.betweenness[index] * 100.0
+        ));
     }
+    Ok(lines)
 }
 
 impl std::fmt::Display for Command {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Command::Help => write!(f, "?"),
-            Command::List => write!(f, "ls"),
-            invalid_command => panic!(
-                "Command '{:?}' is not meant to be stringified!",
-                invalid_command
-            ),
+        write!(f, "{}", self.name().unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn args(input: &str) -> Vec<String> {
+        input.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    fn run(command: Command, input: &str, resources: &mut ScratchResources) -> Vec<String> {
+        command
+            .run(&args(input), &mut resources.context(None))
+            .unwrap()
+            .lines
+    }
+
+    #[test]
+    fn test_note_add_and_delete() {
+        let mut resources = ScratchResources::default();
+        run(Command::Note, "admin pw is hunter2", &mut resources);
+        run(Command::Note, "r01 looks like a honeypot", &mut resources);
+        assert_eq!(
+            resources.notes.0,
+            vec!["admin pw is hunter2", "r01 looks like a honeypot"]
+        );
+
+        run(Command::Note, "-d 1", &mut resources);
+        assert_eq!(resources.notes.0, vec!["r01 looks like a honeypot"]);
+
+        // Out of range deletes leave the notes alone
+        run(Command::Note, "-d 5", &mut resources);
+        assert_eq!(resources.notes.0.len(), 1);
+
+        // And so do deletes that don't say which one
+        assert_eq!(
+            run(Command::Note, "-d", &mut resources),
+            vec!["Delete which? Usage: note -d <n>"]
+        );
+        run(Command::Note, "-d 1 2", &mut resources);
+        assert_eq!(resources.notes.0, vec!["r01 looks like a honeypot"]);
+    }
+
+    #[test]
+    fn test_undo_notes() {
+        let mut resources = ScratchResources {
+            notes: Notes(vec!["one".to_string(), "two".to_string()]),
+            ..Default::default()
+        };
+        let mut context = resources.context(None);
+        Command::Note.run(&args("-d 1"), &mut context).unwrap();
+        Command::Note.run(&args("three"), &mut context).unwrap();
+        assert_eq!(context.notes.0, vec!["two", "three"]);
+
+        Command::Undo.run(&[], &mut context).unwrap();
+        assert_eq!(context.notes.0, vec!["two"]);
+        Command::Undo.run(&[], &mut context).unwrap();
+        assert_eq!(context.notes.0, vec!["one", "two"]);
+
+        // Nothing left to take back
+        let output = Command::Undo.run(&[], &mut context).unwrap();
+        assert_eq!(output.lines, vec!["Nothing to undo."]);
+        assert!(!output.success);
+    }
+
+    #[test]
+    fn test_notes_lists_numbered() {
+        let mut resources = ScratchResources {
+            notes: Notes(vec!["one".to_string(), "two".to_string()]),
+            ..Default::default()
+        };
+        let output = run(Command::Notes, "", &mut resources);
+        assert_eq!(output, vec!["1. one", "2. two"]);
+    }
+
+    #[test]
+    fn test_auto_connect_scans_every_hop() {
+        let graph = NetworkGraph::parse(
+            "type internet i01\ntype router r01\ntype pc ws01\nlink i01 r01\nlink r01 ws01",
+        )
+        .unwrap();
+        let output = Command::Auto
+            .run(
+                &args("connect ws01"),
+                &mut ScratchResources::default().context(Some(&graph)),
+            )
+            .unwrap();
+        assert_eq!(
+            output.lines,
+            vec![
+                "> ls i01",
+                "r01 (router)",
+                "> ls r01",
+                "i01 (internet)",
+                "ws01 (pc)",
+                "> ls ws01",
+                "r01 (router)",
+                "Made it to ws01 in 2 hops.",
+            ]
+        );
+
+        // Levels that hide the neighbors from `ls` hide them from `auto` too
+        let graph =
+            NetworkGraph::parse("type internet i01\ntype pc ws01\nlink i01 ws01\ndeny ls").unwrap();
+        let output = Command::Auto
+            .run(
+                &args("connect ws01"),
+                &mut ScratchResources::default().context(Some(&graph)),
+            )
+            .unwrap();
+        assert!(!output.success);
+        assert_eq!(
+            output.lines,
+            vec!["auto connect: needs ls, which isn't on this rig yet"]
+        );
+    }
+
+    #[test]
+    fn test_ls_wildcards() {
+        let graph = NetworkGraph::parse(
+            "type router r01\ntype pc ws01\ntype pc ws02\nlink r01 ws01\nlink r01 ws02",
+        )
+        .unwrap();
+        let mut resources = ScratchResources::default();
+        let mut context = resources.context(Some(&graph));
+
+        let output = run_line("ls ws*", &mut context).unwrap();
+        assert_eq!(
+            output.lines,
+            vec!["ws01:", "r01 (router)", "ws02:", "r01 (router)"]
+        );
+        // Quoting the wildcard asks for a node that's really called that
+        let output = run_line("ls \"ws*\"", &mut context).unwrap();
+        assert_eq!(output.lines, vec!["Never heard of ws*."]);
+        assert!(!output.success);
+    }
+
+    #[test]
+    fn test_macro_record_and_play() {
+        let mut resources = ScratchResources::default();
+        let mut context = resources.context(None);
+        Command::Macro
+            .run(&args("record jot"), &mut context)
+            .unwrap();
+        // The terminal records lines as they're submitted
+        context.macros.record("note first");
+        context.macros.record("note second");
+        Command::Macro.run(&args("stop"), &mut context).unwrap();
+        assert_eq!(
+            context.macros.saved["jot"],
+            vec!["note first", "note second"]
+        );
+
+        Command::Macro.run(&args("play jot"), &mut context).unwrap();
+        assert_eq!(context.notes.0, vec!["first", "second"]);
+
+        // Recording over an existing macro asks first
+        run_line("macro record jot", &mut context).unwrap();
+        assert!(context.prompt.0.is_some());
+        run_line("n", &mut context).unwrap();
+        assert!(context.macros.recording.is_none());
+        run_line("macro record jot", &mut context).unwrap();
+        run_line("y", &mut context).unwrap();
+        assert!(context.macros.recording.is_some());
+    }
+
+    #[test]
+    fn test_export_asks_before_overwriting() {
+        let graph = NetworkGraph::parse("type router r01").unwrap();
+        let mut resources = ScratchResources::default();
+        let mut context = resources.context(Some(&graph));
+        let path = std::env::temp_dir().join(format!("export-test-{}.dot", std::process::id()));
+        std::fs::write(&path, "keep me").unwrap();
+        let line = format!(
+            "export map {}",
+            tokenizer::escape(&path.display().to_string())
+        );
+
+        run_line(&line, &mut context).unwrap();
+        assert!(context.prompt.0.is_some());
+        assert!(!run_line("n", &mut context).unwrap().success);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "keep me");
+
+        run_line(&line, &mut context).unwrap();
+        assert!(run_line("y", &mut context).unwrap().success);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), graph.to_dot());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_chained_commands() {
+        let mut resources = ScratchResources::default();
+        let mut context = resources.context(None);
+
+        // `&&` stops at the first failure, `;` carries on regardless
+        let output = run_line("note a && bogus && note b; note c", &mut context).unwrap();
+        assert_eq!(context.notes.0, vec!["a", "c"]);
+        assert!(output.success);
+
+        let output = run_line("note d; undo; undo; undo; undo", &mut context).unwrap();
+        assert_eq!(context.notes.0, Vec::<String>::new());
+        assert!(!output.success);
+
+        let output = run_line("bogus; if-ok note skipped; calc $? + 1", &mut context).unwrap();
+        assert_eq!(output.lines.last().map(String::as_str), Some("2"));
+        assert!(context.notes.0.is_empty());
+        run_line("if-ok repeat 3 note x; repeat 3 note y", &mut context).unwrap();
+        assert_eq!(context.notes.0, vec!["y", "y", "y"]);
+
+        // Clearing the screen drops whatever the line printed before it
+        let output = run_line("notes; clear; calc 1 + 1", &mut context).unwrap();
+        assert!(output.clear);
+        assert_eq!(output.lines, vec!["2"]);
+
+        // A restart anywhere in the line restarts once the line's done
+        let output = run_line("repeat 2 restart; note after", &mut context).unwrap();
+        assert!(output.restart);
+        assert_eq!(context.notes.0.last().map(String::as_str), Some("after"));
+
+        // Quoted and escaped separators are just text, even passed on through repeat
+        run_line(
+            r#"repeat 1 note "a; b" c\&\&d && note 'x  y'"#,
+            &mut context,
+        )
+        .unwrap();
+        assert_eq!(
+            context.notes.0[context.notes.0.len() - 2..],
+            ["a; b c&&d", "x  y"]
+        );
+    }
+
+    #[test]
+    fn test_music() {
+        let mut resources = ScratchResources::default();
+        let mut context = resources.context(None);
+
+        let output = run_line("music play monkeys spinning", &mut context).unwrap();
+        assert_eq!(output.lines, vec!["Now playing: Monkeys Spinning Monkeys"]);
+        let output = run_line("music list", &mut context).unwrap();
+        assert_eq!(
+            output.lines,
+            vec!["1. Fluffing A Duck", "2. Monkeys Spinning Monkeys <"]
+        );
+        assert!(!run_line("music play 9", &mut context).unwrap().success);
+        run_line("music next", &mut context).unwrap();
+        assert_eq!(context.jukebox.0, Some(0));
+        run_line("music off", &mut context).unwrap();
+        assert_eq!(resources.jukebox.0, None);
+    }
+
+    #[test]
+    fn test_echo() {
+        let mut resources = ScratchResources::default();
+        assert_eq!(
+            run(Command::Echo, "hi  there", &mut resources),
+            vec!["hi there"]
+        );
+        assert_eq!(run(Command::Echo, "", &mut resources), vec![""]);
+        assert_eq!(expand_tabs("ls\tr01\t"), "ls      r01     ");
+        assert_eq!(expand_tabs("12345678\tx"), "12345678        x");
+    }
+
+    #[test]
+    fn test_every_command_has_help() {
+        assert_eq!(commands_without_help(), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_set() {
+        let mut resources = ScratchResources::default();
+        let mut context = resources.context(None);
+
+        let output = run_line("set", &mut context).unwrap();
+        assert_eq!(output.lines, vec!["editmode emacs", "prompt >"]);
+        assert!(!run_line("set editmode ed", &mut context).unwrap().success);
+        assert!(run_line("set editmode vi", &mut context).unwrap().success);
+        assert!(
+            run_line("set prompt \\u@\\h\\$", &mut context)
+                .unwrap()
+                .success
+        );
+        assert_eq!(resources.edit_mode, TerminalEditMode::Vi);
+        assert_eq!(resources.terminal_prompt.render(), "guest@localhost$ ");
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_line_keeps_every_token(
+            // (quotes and backslashes are the tokenizer's business, so they're left out here)
+            tokens in prop::collection::vec("[^\\s\"'\\\\]{1,8}", 0..8),
+            separators in prop::collection::vec("[ \\t]{1,3}", 8),
+        ) {
+            let line = tokens
+                .iter()
+                .zip(&separators)
+                .map(|(token, separator)| format!("{separator}{token}"))
+                .collect::<String>();
+            let (command, args) = parse_line(&line);
+
+            match tokens.first() {
+                None => {
+                    prop_assert!(matches!(command, Command::Noop));
+                    prop_assert!(args.is_empty());
+                }
+                Some(name) => match Command::parse(name) {
+                    Command::Invalid => prop_assert_eq!(&args, &tokens),
+                    parsed => {
+                        prop_assert_eq!(format!("{command:?}"), format!("{parsed:?}"));
+                        prop_assert_eq!(&args[..], &tokens[1..]);
+                    }
+                },
+            }
+
+            // Whatever the player throws at it, running the command shouldn't panic
+            let result = command.run(&args, &mut ScratchResources::default().context(None));
+            prop_assert!(result.is_ok());
         }
     }
 }
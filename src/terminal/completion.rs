@@ -0,0 +1,112 @@
+//! Tab completion: command names first, then whatever the command says its arguments can be
+//! (see `Command::completions`).
+
+use std::ops::Range;
+
+use super::{
+    command::{self, Command, CommandContext},
+    tokenizer,
+};
+
+/// Completes the word behind the cursor as far as every candidate agrees (adding a space when
+/// there's only one). Returns the byte range of the line to replace, and what to put there.
+pub fn complete(
+    line: &str,
+    cursor: usize,
+    context: &CommandContext,
+) -> Option<(Range<usize>, String)> {
+    // Only the last command of a chain is the one being typed
+    let (_, segment) = *command::split_chain(&line[..cursor]).last()?;
+    let offset = cursor - segment.len();
+
+    // The word being completed is empty if the cursor is after a space
+    let tokens = tokenizer::tokenize(segment);
+    let (prefix_start, prefix, previous) = match tokens.split_last() {
+        Some((token, previous)) if token.range.end == segment.len() => {
+            (token.range.start, token.text.as_str(), previous)
+        }
+        _ => (segment.len(), "", tokens.as_slice()),
+    };
+    let previous = previous
+        .iter()
+        .map(|token| token.text.clone())
+        .collect::<Vec<String>>();
+
+    let candidates = match previous.split_first() {
+        None => context.available_commands().map(String::from).collect(),
+        Some((name, args)) => Command::parse(name).completions(args, context),
+    };
+    let mut matching = candidates
+        .into_iter()
+        .filter(|candidate| candidate.starts_with(prefix));
+
+    let first = matching.next()?;
+    let mut completed = first.clone();
+    let mut unique = true;
+    for other in matching {
+        unique &= other == first;
+        while !other.starts_with(&completed) {
+            completed.pop();
+        }
+    }
+    if !unique && completed.len() == prefix.len() {
+        return None;
+    }
+    // Whatever was typed (quotes and all) is swapped for the completion, escaped to stay one word
+    let mut replacement = tokenizer::escape(&completed);
+    if unique {
+        replacement.push(' ');
+    }
+
+    Some((offset + prefix_start..cursor, replacement))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{game::NetworkGraph, terminal::command::ScratchResources};
+
+    #[test]
+    fn test_complete() {
+        let graph =
+            NetworkGraph::parse("type router r01\ntype pc ws01\ntype pc ws02\nallow ls note notes")
+                .unwrap();
+        let mut resources = ScratchResources::default();
+        let context = resources.context(Some(&graph));
+        let completed_line = |line: &str| {
+            complete(line, line.len(), &context).map(|(range, completed)| {
+                let mut line = line.to_string();
+                line.replace_range(range, &completed);
+                line
+            })
+        };
+
+        // Only the commands the level allows
+        assert_eq!(completed_line("l").as_deref(), Some("ls "));
+        assert_eq!(completed_line("no").as_deref(), Some("note"));
+        assert_eq!(completed_line("ma"), None);
+        // Arguments come from the command
+        assert_eq!(completed_line("ls r").as_deref(), Some("ls r01 "));
+        assert_eq!(completed_line("ls r01 w").as_deref(), Some("ls r01 ws0"));
+        assert_eq!(completed_line("ls r01 ws0"), None);
+        assert_eq!(completed_line("note r"), None);
+        // Quoted words complete too
+        assert_eq!(
+            completed_line("ls \"ws01\" \"r").as_deref(),
+            Some("ls \"ws01\" r01 ")
+        );
+        // Only the last command of a chain
+        assert_eq!(
+            completed_line("ls ws01 && ls r").as_deref(),
+            Some("ls ws01 && ls r01 ")
+        );
+
+        // Every `macro` subcommand
+        let mut resources = ScratchResources::default();
+        let context = resources.context(None);
+        assert_eq!(
+            complete("macro l", 7, &context),
+            Some((6..7, "list ".to_string()))
+        );
+    }
+}
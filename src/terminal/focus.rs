@@ -0,0 +1,150 @@
+//! Which terminal has the keyboard. Only a terminal with [`TerminalFocused`] reads keys, so the
+//! rest of the gameplay screen can have them otherwise: clicking a terminal (or pressing Enter or
+//! Tab while none has the keyboard) focuses it, and Esc (once the line has no use for it) lets go.
+
+use bevy::prelude::*;
+
+use super::{TerminalContainer, TerminalCursor, TerminalInput};
+use crate::{
+    PausableSystems,
+    game::{ObserverMode, observing},
+    screens::Screen,
+};
+
+const FOCUSED_BORDER: Color = Color::WHITE;
+const UNFOCUSED_BORDER: Color = Color::srgb(0.4, 0.4, 0.4);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<TerminalFocused>();
+    app.add_observer(focus_clicked_terminal);
+    app.add_systems(
+        Update,
+        // Observers don't type, so the keyboard stays free for pausing
+        (focus_new_terminals.run_if(not(observing)), show_focus)
+            .chain()
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(
+        Update,
+        // After input, so the key that takes the keyboard back doesn't get typed too
+        focus_terminal_on_key
+            .after(TerminalInput)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay).and(not(observing).and(not(terminal_focused)))),
+    );
+}
+
+/// Marks the terminal cursor that keyboard input goes to
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct TerminalFocused;
+
+/// Run condition for when a terminal has the keyboard
+pub fn terminal_focused(focused_query: Query<(), With<TerminalFocused>>) -> bool {
+    !focused_query.is_empty()
+}
+
+// A level's terminal is ready to type into as soon as it shows up
+fn focus_new_terminals(
+    mut commands: Commands,
+    new_cursor_query: Query<Entity, Added<TerminalCursor>>,
+) {
+    for cursor in &new_cursor_query {
+        commands.entity(cursor).insert(TerminalFocused);
+    }
+}
+
+// Enter or Tab takes the keyboard back after Esc let go of it, for players without a mouse
+fn focus_terminal_on_key(
+    mut commands: Commands,
+    input: Res<ButtonInput<KeyCode>>,
+    cursor_query: Query<Entity, With<TerminalCursor>>,
+) {
+    if !input.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter, KeyCode::Tab]) {
+        return;
+    }
+    if let Some(cursor) = cursor_query.iter().next() {
+        commands.entity(cursor).insert(TerminalFocused);
+    }
+}
+
+// Clicks anywhere on a terminal bubble up to its container, which hands it the keyboard
+fn focus_clicked_terminal(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    observer_mode: Option<Res<ObserverMode>>,
+    container_query: Query<&Children, With<TerminalContainer>>,
+    cursor_query: Query<(), With<TerminalCursor>>,
+    focused_query: Query<Entity, With<TerminalFocused>>,
+) {
+    if observer_mode.is_some_and(|observer_mode| observer_mode.0) {
+        return;
+    }
+    let Ok(children) = container_query.get(trigger.target()) else {
+        return;
+    };
+    let Some(&cursor) = children
+        .into_iter()
+        .find(|&&child| cursor_query.contains(child))
+    else {
+        return;
+    };
+    for focused in focused_query.iter().filter(|&focused| focused != cursor) {
+        commands.entity(focused).remove::<TerminalFocused>();
+    }
+    commands.entity(cursor).insert(TerminalFocused);
+}
+
+// The focused terminal gets a bright border, so it's clear where typing will go
+fn show_focus(
+    cursor_query: Query<(&ChildOf, Has<TerminalFocused>), With<TerminalCursor>>,
+    container_query: Query<&ChildOf, With<TerminalContainer>>,
+    mut border_query: Query<&mut BorderColor>,
+) {
+    for (cursor_parent, focused) in &cursor_query {
+        let Ok(container_parent) = container_query.get(cursor_parent.parent()) else {
+            continue;
+        };
+        if let Ok(mut border) = border_query.get_mut(container_parent.parent()) {
+            border.set_if_neq(BorderColor(if focused {
+                FOCUSED_BORDER
+            } else {
+                UNFOCUSED_BORDER
+            }));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn test_new_terminals_take_focus() {
+        let mut app = App::new();
+        app.add_systems(Update, focus_new_terminals);
+        assert!(!app.world_mut().run_system_once(terminal_focused).unwrap());
+
+        app.world_mut().spawn(TerminalCursor::default());
+        app.update();
+        assert!(app.world_mut().run_system_once(terminal_focused).unwrap());
+    }
+
+    #[test]
+    fn test_enter_takes_focus_back() {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app.add_systems(Update, focus_terminal_on_key);
+        app.world_mut().spawn(TerminalCursor::default());
+        app.update();
+        assert!(!app.world_mut().run_system_once(terminal_focused).unwrap());
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Enter);
+        app.update();
+        assert!(app.world_mut().run_system_once(terminal_focused).unwrap());
+    }
+}
@@ -0,0 +1,115 @@
+//! `*` and `?` wildcards in command arguments, expanded against the names the player can see.
+
+use thiserror::Error;
+
+use super::tokenizer::Token;
+
+// A glob matching more than this is almost certainly a typo (and would flood the terminal)
+const MAX_MATCHES: usize = 32;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum GlobError {
+    #[error("Nothing matches {0}")]
+    NoMatches(String),
+    #[error("{0} matches more than {MAX_MATCHES} things. Narrow it down.")]
+    TooManyMatches(String),
+}
+
+/// Whether `name` matches `pattern`, where `*` is any run of characters and `?` any one
+pub fn matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<char>>();
+    let name = name.chars().collect::<Vec<char>>();
+
+    // Classic two-pointer wildcard match, backtracking to the last `*` on a mismatch
+    let (mut p, mut n) = (0, 0);
+    let mut last_star = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                last_star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match last_star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    last_star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Replaces every argument containing a wildcard with the names it matches, in order. Quoted
+/// arguments are left as they are, like in a shell, so a literal `*` can still be passed.
+pub fn expand<'a>(
+    args: &[Token],
+    names: impl Iterator<Item = &'a str> + Clone,
+) -> Result<Vec<String>, GlobError> {
+    let mut expanded = Vec::new();
+    for Token {
+        text: arg, quoted, ..
+    } in args
+    {
+        if *quoted || !arg.contains(['*', '?']) {
+            expanded.push(arg.clone());
+            continue;
+        }
+
+        let matched = names
+            .clone()
+            .filter(|name| matches(arg, name))
+            .map(str::to_string)
+            .collect::<Vec<String>>();
+        match matched.len() {
+            0 => return Err(GlobError::NoMatches(arg.clone())),
+            n if n > MAX_MATCHES => return Err(GlobError::TooManyMatches(arg.clone())),
+            _ => expanded.extend(matched),
+        }
+    }
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terminal::tokenizer::tokenize;
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("ws0?", "ws01"));
+        assert!(!matches("ws0?", "ws010"));
+        assert!(matches("*", ""));
+        assert!(matches("r*1", "r01"));
+        assert!(matches("*0*", "ws01"));
+        assert!(!matches("s*", "ws01"));
+    }
+
+    #[test]
+    fn test_expand() {
+        let names = ["ws01", "ws02", "r01"];
+        assert_eq!(
+            expand(&tokenize("ws* f01"), names.into_iter()),
+            Ok(vec![
+                "ws01".to_string(),
+                "ws02".to_string(),
+                "f01".to_string()
+            ])
+        );
+        assert_eq!(
+            expand(&tokenize("x*"), names.into_iter()),
+            Err(GlobError::NoMatches("x*".to_string()))
+        );
+        // Quoted wildcards are just characters
+        assert_eq!(
+            expand(&tokenize(r#""ws*" 'r0?'"#), names.into_iter()),
+            Ok(vec!["ws*".to_string(), "r0?".to_string()])
+        );
+    }
+}
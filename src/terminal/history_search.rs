@@ -0,0 +1,116 @@
+//! Reverse incremental search through the lines run so far (Ctrl+R), like readline's: typing
+//! narrows it down to the newest line containing what's typed, Ctrl+R again looks further
+//! back, and any other key takes the line found for editing (Enter runs it).
+
+use super::TerminalCursor;
+
+// Oldest lines are forgotten past this
+const PAST_LINES_LIMIT: usize = 500;
+
+/// A reverse search in progress
+#[derive(Debug, Default)]
+pub(super) struct HistorySearch {
+    pub query: String,
+    // How many matches back from the newest the search is at
+    skip: usize,
+}
+
+impl TerminalCursor {
+    /// Remembers a line that was run, for searching later
+    pub(super) fn remember_line(&mut self, line: &str) {
+        if line.trim().is_empty() {
+            return;
+        }
+        if self.past_lines.len() == PAST_LINES_LIMIT {
+            self.past_lines.remove(0);
+        }
+        self.past_lines.push(line.to_string());
+    }
+
+    /// The line the search has found, if any
+    pub(super) fn search_match(&self) -> Option<&str> {
+        let search = self.search.as_ref()?;
+        self.past_lines
+            .iter()
+            .rev()
+            .filter(|line| line.contains(&search.query))
+            .nth(search.skip)
+            .map(String::as_str)
+    }
+
+    /// Ctrl+R: starts a search, or looks for an older match if already searching
+    pub(super) fn search_older(&mut self) {
+        match &mut self.search {
+            None => self.search = Some(HistorySearch::default()),
+            Some(search) => {
+                let matches = self
+                    .past_lines
+                    .iter()
+                    .filter(|line| line.contains(&search.query))
+                    .count();
+                // Stay on the oldest match rather than losing it
+                if search.skip + 1 < matches {
+                    search.skip += 1;
+                }
+            }
+        }
+    }
+
+    pub(super) fn search_type(&mut self, text: &str) {
+        if let Some(search) = &mut self.search {
+            search
+                .query
+                .extend(text.chars().filter(|c| !c.is_control()));
+            search.skip = 0;
+        }
+    }
+
+    pub(super) fn search_backspace(&mut self) {
+        if let Some(search) = &mut self.search {
+            search.query.pop();
+            search.skip = 0;
+        }
+    }
+
+    /// Ends the search, putting the line it found (if it found one) up for editing
+    pub(super) fn finish_search(&mut self) {
+        if let Some(found) = self.search_match() {
+            // Continued lines come back as one
+            self.current_input = found.replace('\n', " ");
+            self.cursor_location = self.current_input.len();
+        }
+        self.search = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reverse_search() {
+        let mut cursor = TerminalCursor::default();
+        for line in ["ls r01", "note router", "ls ws01", ""] {
+            cursor.remember_line(line);
+        }
+
+        cursor.search_older();
+        cursor.search_type("ls");
+        assert_eq!(cursor.search_match(), Some("ls ws01"));
+        cursor.search_older();
+        assert_eq!(cursor.search_match(), Some("ls r01"));
+        // Nothing older, so it stays put
+        cursor.search_older();
+        assert_eq!(cursor.search_match(), Some("ls r01"));
+
+        cursor.search_backspace();
+        cursor.search_backspace();
+        cursor.search_type("rout");
+        assert_eq!(cursor.search_match(), Some("note router"));
+
+        cursor.finish_search();
+        assert!(cursor.search.is_none());
+        assert_eq!(cursor.current_input, "note router");
+        assert_eq!(cursor.cursor_location, 11);
+    }
+}
@@ -0,0 +1,130 @@
+//! Composed text input: IMEs (for CJK and the like) and dead keys (`´` then `e` for `é`) hand
+//! over finished text in one go rather than a key at a time, and show what's being composed
+//! ("preedit" text) until then. The preedit shows at the cursor, the way it would in a real
+//! terminal.
+
+use bevy::{
+    prelude::*,
+    window::{Ime, PrimaryWindow},
+};
+
+use super::{
+    TerminalCursor, TerminalEditMode, TerminalInput, TerminalState, TerminalWindow,
+    focus::TerminalFocused,
+};
+use crate::screens::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (enable_ime, ime_input.in_set(TerminalInput))
+            .run_if(in_state(Screen::Gameplay).and(in_state(TerminalState::Ready))),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), disable_ime);
+}
+
+impl TerminalCursor {
+    /// Types composed text, the same way keys typing it one at a time would
+    fn commit(&mut self, text: &str, edit_mode: TerminalEditMode) {
+        self.preedit.clear();
+        if self.search.is_some() {
+            self.search_type(text);
+            return;
+        }
+        if self.vi.normal && edit_mode == TerminalEditMode::Vi {
+            for key in text.chars() {
+                self.vi_key(key);
+            }
+            return;
+        }
+        let before = self.snapshot();
+        if self.overwrite {
+            self.overwrite(text);
+        } else {
+            self.insert(text);
+        }
+        self.record_edit(before, Some(text));
+    }
+}
+
+// Only the window the terminal takes input from gets IME input, since the candidate window
+// follows whichever window has it on (and none does while the terminal isn't focused)
+fn enable_ime(
+    terminal_window: Res<TerminalWindow>,
+    primary_window_query: Query<Entity, With<PrimaryWindow>>,
+    focused_query: Query<(), With<TerminalFocused>>,
+    mut window_query: Query<(Entity, &mut Window)>,
+) {
+    let input_window = terminal_window
+        .0
+        .or(primary_window_query.single().ok())
+        .filter(|_| !focused_query.is_empty());
+    for (entity, mut window) in &mut window_query {
+        let wanted = Some(entity) == input_window;
+        if window.ime_enabled != wanted {
+            window.ime_enabled = wanted;
+        }
+    }
+}
+
+fn disable_ime(mut window_query: Query<&mut Window>) {
+    for mut window in &mut window_query {
+        window.ime_enabled = false;
+    }
+}
+
+fn ime_input(
+    mut ime_events: EventReader<Ime>,
+    mut terminal_cursor_query: Query<&mut TerminalCursor, With<TerminalFocused>>,
+    terminal_window: Res<TerminalWindow>,
+    primary_window_query: Query<Entity, With<PrimaryWindow>>,
+    edit_mode: Res<TerminalEditMode>,
+) {
+    // Like keys, composed text only goes to the focused terminal
+    let Some(mut terminal_cursor) = terminal_cursor_query.iter_mut().next() else {
+        return;
+    };
+    let input_window = terminal_window.0.or(primary_window_query.single().ok());
+
+    for event in ime_events.read() {
+        match event {
+            Ime::Preedit { window, value, .. } if Some(*window) == input_window => {
+                terminal_cursor.preedit.clone_from(value);
+            }
+            Ime::Commit { window, value } if Some(*window) == input_window => {
+                terminal_cursor.commit(value, *edit_mode);
+            }
+            Ime::Disabled { .. } => terminal_cursor.preedit.clear(),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit() {
+        let mut cursor = TerminalCursor {
+            preedit: "ｎ".to_string(),
+            ..default()
+        };
+        cursor.commit("日本", TerminalEditMode::Emacs);
+        assert_eq!(cursor.current_input, "日本");
+        assert!(cursor.preedit.is_empty());
+
+        // Composed text is one undo step, like a typed word
+        cursor.undo();
+        assert_eq!(cursor.current_input, "");
+
+        // While searching, it's what's searched for
+        cursor.search_older();
+        cursor.commit("é", TerminalEditMode::Emacs);
+        assert_eq!(cursor.current_input, "");
+        assert_eq!(
+            cursor.search.map(|search| search.query).as_deref(),
+            Some("é")
+        );
+    }
+}
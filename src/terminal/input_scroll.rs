@@ -0,0 +1,96 @@
+//! Sideways scrolling for input lines too long for the terminal: only the part around the
+//! cursor shows, with `<` and `>` where the line carries on out of view.
+
+use bevy::prelude::*;
+
+use super::FONT_SIZE;
+
+/// How wide a character is in the terminal font (VT323 is monospaced, 0.4em wide)
+pub(super) const CHAR_WIDTH: f32 = FONT_SIZE * 0.4;
+
+/// How far along the line being typed the view has scrolled, in characters
+#[derive(Component, Debug, Default)]
+pub(super) struct InputScroll(usize);
+
+/// The part of a line that shows
+#[derive(Debug)]
+pub(super) struct VisibleLine<'a> {
+    pub graphemes: &'a [&'a str],
+    /// Where the cursor is among them
+    pub cursor: usize,
+    pub clipped_left: bool,
+    pub clipped_right: bool,
+}
+
+impl InputScroll {
+    /// Scrolls only as far as it takes to keep the cursor in view in `columns` characters, and
+    /// returns what shows
+    pub(super) fn view<'a>(
+        &mut self,
+        graphemes: &'a [&'a str],
+        cursor: usize,
+        columns: usize,
+    ) -> VisibleLine<'a> {
+        // The cursor can sit just past the end
+        let length = graphemes.len() + 1;
+        if length <= columns {
+            self.0 = 0;
+            return VisibleLine {
+                graphemes,
+                cursor,
+                clipped_left: false,
+                clipped_right: false,
+            };
+        }
+
+        // Room for the line between the markers
+        let width = columns.saturating_sub(2).max(1);
+        self.0 = self
+            .0
+            .min(cursor)
+            .max((cursor + 1).saturating_sub(width))
+            .min(length - width);
+        let end = (self.0 + width).min(graphemes.len());
+        VisibleLine {
+            graphemes: &graphemes[self.0..end],
+            cursor: cursor - self.0,
+            clipped_left: self.0 > 0,
+            clipped_right: end < graphemes.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    use super::*;
+
+    #[test]
+    fn test_view() {
+        let line = "connect ws01".graphemes(true).collect::<Vec<_>>();
+        let mut scroll = InputScroll::default();
+
+        // Short enough to fit: nothing to do
+        let view = scroll.view(&line, 12, 13);
+        assert_eq!(view.graphemes.len(), 12);
+        assert!(!view.clipped_left && !view.clipped_right);
+
+        // At the end, the start scrolls out of view
+        let view = scroll.view(&line, 12, 8);
+        assert_eq!(view.graphemes.concat(), " ws01");
+        assert_eq!(view.cursor, 5);
+        assert!(view.clipped_left && !view.clipped_right);
+
+        // Moving left doesn't scroll until the cursor reaches the edge
+        assert_eq!(scroll.view(&line, 8, 8).graphemes.concat(), " ws01");
+        let view = scroll.view(&line, 0, 8);
+        assert_eq!(view.graphemes.concat(), "connec");
+        assert!(!view.clipped_left && view.clipped_right);
+
+        let view = scroll.view(&line, 6, 8);
+        assert_eq!(view.graphemes.concat(), "onnect");
+        assert_eq!(view.cursor, 5);
+        assert!(view.clipped_left && view.clipped_right);
+    }
+}
@@ -0,0 +1,126 @@
+//! Repeating a held key in the terminal at a steady, configurable pace, rather than whatever
+//! the OS (or browser) does with its own repeats.
+
+use bevy::{
+    ecs::system::SystemParam,
+    input::{ButtonState, keyboard::KeyboardInput},
+    prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<KeyRepeat>();
+    app.init_resource::<KeyRepeat>();
+    app.add_event::<KeyRepeated>();
+}
+
+/// How quickly a held key repeats
+#[derive(Resource, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource)]
+pub enum KeyRepeat {
+    Off,
+    Slow,
+    #[default]
+    Normal,
+    Fast,
+}
+
+impl KeyRepeat {
+    /// Seconds before the first repeat, and between repeats after that
+    fn timing(self) -> Option<(f32, f32)> {
+        match self {
+            KeyRepeat::Off => None,
+            KeyRepeat::Slow => Some((0.6, 1.0 / 10.0)),
+            KeyRepeat::Normal => Some((0.4, 1.0 / 20.0)),
+            KeyRepeat::Fast => Some((0.25, 1.0 / 30.0)),
+        }
+    }
+
+    /// The next setting along, for cycling through them in settings
+    pub fn next(self) -> Self {
+        match self {
+            KeyRepeat::Off => KeyRepeat::Slow,
+            KeyRepeat::Slow => KeyRepeat::Normal,
+            KeyRepeat::Normal => KeyRepeat::Fast,
+            KeyRepeat::Fast => KeyRepeat::Off,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyRepeat::Off => "Off",
+            KeyRepeat::Slow => "Slow",
+            KeyRepeat::Normal => "Normal",
+            KeyRepeat::Fast => "Fast",
+        }
+    }
+}
+
+/// Another press of the key being held down
+#[derive(Event, Debug)]
+pub(super) struct KeyRepeated(KeyboardInput);
+
+/// Key presses for the terminal: the real ones, plus our repeats of a held key
+#[derive(SystemParam)]
+pub(super) struct TerminalKeys<'w, 's> {
+    presses: EventReader<'w, 's, KeyboardInput>,
+    repeats: EventReader<'w, 's, KeyRepeated>,
+}
+
+impl TerminalKeys<'_, '_> {
+    pub fn read(&mut self) -> impl Iterator<Item = &KeyboardInput> + '_ {
+        // The OS's own repeats are dropped in favour of ours
+        self.presses
+            .read()
+            .filter(|event| !event.repeat)
+            .chain(self.repeats.read().map(|repeated| &repeated.0))
+    }
+
+    /// Skips the keys pressed so far, for when nothing's reading them
+    pub fn clear(&mut self) {
+        self.presses.clear();
+        self.repeats.clear();
+    }
+}
+
+/// Repeats the last key pressed for as long as it's held
+pub(super) fn repeat_held_keys(
+    mut input_event_reader: EventReader<KeyboardInput>,
+    mut repeated: EventWriter<KeyRepeated>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    key_repeat: Res<KeyRepeat>,
+    time: Res<Time>,
+    mut held: Local<Option<(KeyboardInput, Timer)>>,
+) {
+    let Some((delay, interval)) = key_repeat.timing() else {
+        *held = None;
+        return;
+    };
+
+    for event in input_event_reader.read().filter(|event| !event.repeat) {
+        if event.state == ButtonState::Pressed {
+            *held = Some((event.clone(), Timer::from_seconds(delay, TimerMode::Once)));
+        } else if held
+            .as_ref()
+            .is_some_and(|(pressed, _)| pressed.key_code == event.key_code)
+        {
+            *held = None;
+        }
+    }
+
+    // The release can get lost (e.g. to another window), so check it's really still down
+    let Some((event, timer)) = held
+        .as_mut()
+        .filter(|(event, _)| keyboard.pressed(event.key_code))
+    else {
+        *held = None;
+        return;
+    };
+
+    if timer.tick(time.delta()).just_finished() {
+        repeated.write(KeyRepeated(KeyboardInput {
+            repeat: true,
+            ..event.clone()
+        }));
+        *timer = Timer::from_seconds(interval, TimerMode::Repeating);
+    }
+}
@@ -0,0 +1,238 @@
+//! Which keys do what while editing the terminal line. The bindings live in the
+//! [`TerminalKeymap`] resource, so they can be changed without touching the input system.
+//! Keys that aren't bound to anything type their text as usual.
+
+use std::collections::HashMap;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<TerminalKeymap>();
+    app.init_resource::<TerminalKeymap>();
+}
+
+/// Something a key can do to the terminal line
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditAction {
+    /// Run the line (or continue it, after a `\`)
+    Submit,
+    Paste,
+    DeleteBackward,
+    DeleteForward,
+    DeleteWordBackward,
+    MoveLeft,
+    MoveRight,
+    MoveWordLeft,
+    MoveWordRight,
+    MoveHome,
+    MoveEnd,
+    KillToStart,
+    KillToEnd,
+    Yank,
+    Undo,
+    Redo,
+    ClearScreen,
+    /// Copy the selected history, or abandon the line if nothing's selected
+    Interrupt,
+    Complete,
+    /// Switch between inserting and overwriting what's typed
+    ToggleOverwrite,
+    /// Search back through the lines run so far
+    ReverseSearch,
+    /// Leave a search, or stop typing and start using vi motions (in the vi edit mode)
+    Leave,
+}
+
+/// A key and the modifiers held with it
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub key: KeyCode,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+}
+
+impl KeyChord {
+    pub const fn key(key: KeyCode) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            alt: false,
+            shift: false,
+        }
+    }
+
+    pub const fn ctrl(key: KeyCode) -> Self {
+        Self {
+            ctrl: true,
+            ..Self::key(key)
+        }
+    }
+
+    pub const fn alt(key: KeyCode) -> Self {
+        Self {
+            alt: true,
+            ..Self::key(key)
+        }
+    }
+
+    pub const fn shift(key: KeyCode) -> Self {
+        Self {
+            shift: true,
+            ..Self::key(key)
+        }
+    }
+
+    /// Whether the chord is a shortcut (zoom, panel resizing...) rather than typing `text`.
+    /// Windows reports AltGr as Ctrl+Alt, so that still types, as long as the key has text.
+    pub fn is_shortcut(&self, text: Option<&str>) -> bool {
+        self.ctrl && (!self.alt || text.is_none_or(str::is_empty))
+    }
+}
+
+/// Key chords and the editing actions they're bound to
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct TerminalKeymap {
+    pub bindings: HashMap<KeyChord, EditAction>,
+}
+
+impl Default for TerminalKeymap {
+    fn default() -> Self {
+        use KeyCode::*;
+
+        let bindings = [
+            (KeyChord::key(Enter), EditAction::Submit),
+            (KeyChord::ctrl(KeyV), EditAction::Paste),
+            (KeyChord::shift(Insert), EditAction::Paste),
+            (KeyChord::key(Backspace), EditAction::DeleteBackward),
+            (KeyChord::key(Delete), EditAction::DeleteForward),
+            (KeyChord::ctrl(Backspace), EditAction::DeleteWordBackward),
+            (KeyChord::alt(Backspace), EditAction::DeleteWordBackward),
+            (KeyChord::key(ArrowLeft), EditAction::MoveLeft),
+            (KeyChord::key(ArrowRight), EditAction::MoveRight),
+            (KeyChord::ctrl(ArrowLeft), EditAction::MoveWordLeft),
+            (KeyChord::ctrl(ArrowRight), EditAction::MoveWordRight),
+            (KeyChord::key(Home), EditAction::MoveHome),
+            (KeyChord::key(End), EditAction::MoveEnd),
+            (KeyChord::ctrl(KeyU), EditAction::KillToStart),
+            (KeyChord::ctrl(KeyK), EditAction::KillToEnd),
+            (KeyChord::ctrl(KeyY), EditAction::Yank),
+            (KeyChord::ctrl(KeyZ), EditAction::Undo),
+            (
+                KeyChord {
+                    shift: true,
+                    ..KeyChord::ctrl(KeyZ)
+                },
+                EditAction::Redo,
+            ),
+            (KeyChord::ctrl(KeyL), EditAction::ClearScreen),
+            (KeyChord::ctrl(KeyC), EditAction::Interrupt),
+            (KeyChord::key(Tab), EditAction::Complete),
+            (KeyChord::key(Insert), EditAction::ToggleOverwrite),
+            (KeyChord::ctrl(KeyR), EditAction::ReverseSearch),
+            (KeyChord::key(Escape), EditAction::Leave),
+        ];
+        Self {
+            bindings: bindings.into_iter().collect(),
+        }
+    }
+}
+
+impl TerminalKeymap {
+    /// What a chord does. Modifiers a chord isn't bound with are let slide, so Shift+Left still
+    /// moves left, but Ctrl always has to be part of the binding.
+    pub fn action(&self, chord: KeyChord) -> Option<EditAction> {
+        let unshifted = KeyChord {
+            shift: false,
+            ..chord
+        };
+        let bare = (!chord.ctrl).then_some(KeyChord::key(chord.key));
+        [Some(chord), Some(unshifted), bare]
+            .into_iter()
+            .flatten()
+            .find_map(|chord| self.bindings.get(&chord).copied())
+    }
+}
+
+/// The keymap, and the keyboard to tell which modifiers are held
+#[derive(SystemParam)]
+pub(super) struct TerminalChords<'w> {
+    keyboard: Res<'w, ButtonInput<KeyCode>>,
+    keymap: Res<'w, TerminalKeymap>,
+}
+
+impl TerminalChords<'_> {
+    /// The key with whatever modifiers are held right now
+    pub fn chord(&self, key: KeyCode) -> KeyChord {
+        KeyChord {
+            key,
+            ctrl: self
+                .keyboard
+                .any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]),
+            alt: self
+                .keyboard
+                .any_pressed([KeyCode::AltLeft, KeyCode::AltRight]),
+            shift: self
+                .keyboard
+                .any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+        }
+    }
+
+    pub fn action(&self, chord: KeyChord) -> Option<EditAction> {
+        self.keymap.action(chord)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action() {
+        let keymap = TerminalKeymap::default();
+        let chord = |key, ctrl, alt, shift| KeyChord {
+            key,
+            ctrl,
+            alt,
+            shift,
+        };
+
+        assert_eq!(
+            keymap.action(chord(KeyCode::ArrowLeft, false, false, true)),
+            Some(EditAction::MoveLeft)
+        );
+        assert_eq!(
+            keymap.action(chord(KeyCode::KeyZ, true, false, true)),
+            Some(EditAction::Redo)
+        );
+        assert_eq!(
+            keymap.action(chord(KeyCode::KeyV, true, false, true)),
+            Some(EditAction::Paste)
+        );
+        // Shift+Insert is its own binding, not Insert with Shift let slide
+        assert_eq!(
+            keymap.action(chord(KeyCode::Insert, false, false, true)),
+            Some(EditAction::Paste)
+        );
+        // Ctrl doesn't slide: Ctrl+Tab isn't Tab
+        assert_eq!(keymap.action(chord(KeyCode::Tab, true, false, false)), None);
+        assert_eq!(
+            keymap.action(chord(KeyCode::KeyA, false, false, false)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_shortcuts() {
+        let ctrl_alt = KeyChord {
+            ctrl: true,
+            ..KeyChord::alt(KeyCode::KeyQ)
+        };
+        assert!(KeyChord::ctrl(KeyCode::KeyQ).is_shortcut(Some("q")));
+        // AltGr+Q is `@` on a German layout, which Windows sends as Ctrl+Alt+Q
+        assert!(!ctrl_alt.is_shortcut(Some("@")));
+        assert!(ctrl_alt.is_shortcut(None));
+        assert!(!KeyChord::alt(KeyCode::KeyQ).is_shortcut(Some("q")));
+    }
+}
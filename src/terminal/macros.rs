@@ -0,0 +1,33 @@
+//! Named sequences of terminal commands the player can record and replay.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+
+/// Recorded macros, plus the one currently being recorded (if any).
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct Macros {
+    pub saved: HashMap<String, Vec<String>>,
+    // Name and lines so far of the macro being recorded
+    pub recording: Option<(String, Vec<String>)>,
+}
+
+impl Macros {
+    /// Adds an executed line to the macro being recorded, if there is one
+    pub fn record(&mut self, line: &str) {
+        if let Some((_, lines)) = &mut self.recording {
+            lines.push(line.trim().to_string());
+        }
+    }
+
+    /// Finishes recording, returning the name of the saved macro and whatever it replaced
+    pub fn stop(&mut self) -> Option<(String, Option<Vec<String>>)> {
+        let (name, lines) = self.recording.take()?;
+        let previous = self.saved.insert(name.clone(), lines);
+        Some((name, previous))
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Macros>();
+    app.init_resource::<Macros>();
+}
@@ -1,40 +1,377 @@
+mod attract;
+mod calc;
+mod captions;
+mod clicks;
+mod clipboard;
 mod command;
+mod completion;
+mod focus;
+mod glob;
+mod history_search;
+mod ime;
+mod input_scroll;
+mod key_repeat;
+mod keymap;
+mod macros;
+mod notes;
+mod prompt;
+mod responses;
+mod selection;
+mod shell_prompt;
+mod speech;
 mod terminal_assets;
+mod timestamps;
+mod tokenizer;
+mod undo;
+mod vi;
+mod wiki;
 
+use std::{collections::VecDeque, ops::Range};
+
+pub use attract::{TerminalAutotype, autotype_terminal};
 use bevy::{
+    ecs::{spawn::SpawnIter, system::SystemParam},
     input::{
         ButtonState,
-        keyboard::KeyboardInput,
         mouse::{MouseScrollUnit, MouseWheel},
     },
     picking::hover::HoverMap,
     prelude::*,
     text::LineHeight,
+    window::PrimaryWindow,
 };
-use command::Command;
-use rand::seq::SliceRandom;
+#[cfg(not(target_family = "wasm"))]
+pub use clicks::CUSTOM_CLICKS_SOURCE;
+pub use clicks::ClickPack;
+#[cfg(feature = "analytics")]
+pub use command::CommandsRan;
+pub use command::commands_without_help;
+use command::{CommandContext, CommandOutput, LastStatus};
+use focus::TerminalFocused;
+pub use focus::terminal_focused;
+use history_search::HistorySearch;
+use input_scroll::{CHAR_WIDTH, InputScroll};
+pub use key_repeat::KeyRepeat;
+use key_repeat::TerminalKeys;
+pub use keymap::EditAction;
+use keymap::TerminalChords;
+use macros::Macros;
+use notes::Notes;
+pub use notes::notes_panel;
+use prompt::PendingPrompt;
+pub use responses::FamilyFriendly;
+use responses::TerminalText;
+use selection::{HistoryEntry, HistorySelection};
+pub use shell_prompt::TerminalPrompt;
+pub use speech::TerminalSpeech;
+use speech::{Babble, babble};
 pub use terminal_assets::TerminalAssets;
+pub use timestamps::TerminalTimestamps;
+use timestamps::{RunClock, TIMESTAMP_TEXT};
+use undo::UndoHistory;
+use unicode_segmentation::UnicodeSegmentation;
+pub use vi::TerminalEditMode;
+use vi::ViState;
+pub use wiki::Wiki;
 
-use crate::{asset_tracking::LoadResource, audio::sound_effect, screens::Screen};
+use crate::{
+    asset_tracking::LoadResource,
+    crash,
+    game::{LevelAssets, NetworkGraph, Sandbox, observing},
+    haptics::HapticPulse,
+    jukebox::Jukebox,
+    screens::Screen,
+    selftest::SelfTest,
+};
 
 const FONT_SIZE: f32 = 20.0;
 const LINE_HEIGHT: f32 = 21.0;
-const TERMINAL_CURSOR: &str = "> ";
+// Shown in place of the cursor prompt on lines continuing one that ended with `\`
+const CONTINUATION_PROMPT: &str = ".. ";
+const CURSOR_BLOCK: char = '█';
+// The cursor in overwrite mode
+const CURSOR_UNDERSCORE: char = '_';
+const CURSOR_BLINK_SECS: f32 = 0.53;
+const KILL_RING_SIZE: usize = 8;
+// Oldest line edits can't be undone past this
+const EDIT_UNDO_LIMIT: usize = 64;
 
 #[derive(Component)]
 struct TerminalContainer;
 
+/// The line being typed into a terminal
 #[derive(Component, Debug, Default)]
-struct TerminalCursor {
+pub struct TerminalCursor {
     // Holds the current line to eventually be processed
     current_input: String,
-    // Cursor location to figure out input/deletion
+    // Cursor location to figure out input/deletion (a byte offset, between characters)
     cursor_location: usize,
+    // Recently killed (cut) text, newest last, for yanking back
+    kill_ring: VecDeque<String>,
+    // Earlier lines of the input, each of which ended with `\` (stripped), waiting for the rest
+    continued: Vec<String>,
+    // The line (and cursor) before each edit, newest last, for Ctrl+Z, and undone edits for
+    // Ctrl+Shift+Z
+    undo_edits: VecDeque<(String, usize)>,
+    redo_edits: Vec<(String, usize)>,
+    // Whether the last edit was typing a word, so more typing joins the same undo step
+    typing: bool,
+    // Normal or insert mode, when editing vi-style
+    vi: ViState,
+    // Lines run so far, oldest first, and the Ctrl+R search through them if one's going
+    past_lines: Vec<String>,
+    search: Option<HistorySearch>,
+    // Whether typing replaces the character under the cursor (toggled with Insert)
+    overwrite: bool,
+    // Text an IME (or a dead key) is still composing, shown at the cursor until it's committed
+    preedit: String,
+    // Whether the line is a secret answer (to a masked question), kept out of the kill ring
+    secret: bool,
+}
+
+impl TerminalCursor {
+    /// Inserts text at the cursor, skipping control characters, and moves the cursor past it
+    fn insert(&mut self, text: &str) {
+        let text = text.chars().filter(|c| !c.is_control()).collect::<String>();
+        self.current_input.insert_str(self.cursor_location, &text);
+        self.cursor_location += text.len();
+    }
+
+    /// Types text over the characters at the cursor, adding to the end of the line once it
+    /// runs out
+    fn overwrite(&mut self, text: &str) {
+        let text = text.chars().filter(|c| !c.is_control()).collect::<String>();
+        for grapheme in text.graphemes(true) {
+            let replaced = self.next_boundary().unwrap_or(self.cursor_location);
+            self.current_input
+                .replace_range(self.cursor_location..replaced, grapheme);
+            self.cursor_location += grapheme.len();
+        }
+    }
+
+    // Characters are graphemes here: what the player sees as one character, which can be
+    // several chars (`e` and a combining accent, or an emoji with a skin tone)
+
+    /// Where the character behind the cursor starts
+    fn previous_boundary(&self) -> Option<usize> {
+        self.current_input
+            .grapheme_indices(true)
+            .map(|(start, _)| start)
+            .take_while(|&start| start < self.cursor_location)
+            .last()
+    }
+
+    /// Where the character ahead of the cursor ends
+    fn next_boundary(&self) -> Option<usize> {
+        self.current_input
+            .grapheme_indices(true)
+            .map(|(start, grapheme)| start + grapheme.len())
+            .find(|&end| end > self.cursor_location)
+    }
+
+    /// Deletes the character behind the cursor (Backspace)
+    fn delete_backward(&mut self) {
+        if let Some(start) = self.previous_boundary() {
+            self.current_input
+                .replace_range(start..self.cursor_location, "");
+            self.cursor_location = start;
+        }
+    }
+
+    /// Deletes the character ahead of the cursor (Del)
+    fn delete_forward(&mut self) {
+        if let Some(end) = self.next_boundary() {
+            self.current_input
+                .replace_range(self.cursor_location..end, "");
+        }
+    }
+
+    /// Moves the cursor one character to the left (Left arrow)
+    fn move_left(&mut self) {
+        if let Some(start) = self.previous_boundary() {
+            self.cursor_location = start;
+        }
+    }
+
+    /// Moves the cursor one character to the right (Right arrow)
+    fn move_right(&mut self) {
+        if let Some(end) = self.next_boundary() {
+            self.cursor_location = end;
+        }
+    }
+
+    /// Moves the cursor to the start of the line (Home)
+    fn move_home(&mut self) {
+        self.cursor_location = 0;
+    }
+
+    /// Moves the cursor to the end of the line (End)
+    fn move_end(&mut self) {
+        self.cursor_location = self.current_input.len();
+    }
+
+    /// Moves the cursor to the start of the word it's in, or the previous one (Ctrl+Left)
+    fn move_word_left(&mut self) {
+        self.cursor_location = command::words(&self.current_input)
+            .map(|word| word.start)
+            .filter(|&start| start < self.cursor_location)
+            .last()
+            .unwrap_or(0);
+    }
+
+    /// Moves the cursor to the end of the word it's in, or the next one (Ctrl+Right)
+    fn move_word_right(&mut self) {
+        self.cursor_location = command::words(&self.current_input)
+            .map(|word| word.end)
+            .find(|&end| end > self.cursor_location)
+            .unwrap_or(self.current_input.len());
+    }
+
+    /// Deletes from the cursor back to the start of the word (Ctrl/Alt+Backspace)
+    fn delete_word_backward(&mut self) {
+        let end = self.cursor_location;
+        self.move_word_left();
+        self.kill(self.cursor_location..end);
+    }
+
+    /// Kills everything before the cursor (Ctrl+U)
+    fn kill_to_start(&mut self) {
+        let end = self.cursor_location;
+        self.cursor_location = 0;
+        self.kill(0..end);
+    }
+
+    /// Kills everything from the cursor on (Ctrl+K)
+    fn kill_to_end(&mut self) {
+        self.kill(self.cursor_location..self.current_input.len());
+    }
+
+    /// Puts the most recently killed text back at the cursor (Ctrl+Y)
+    fn yank(&mut self) {
+        if let Some(killed) = self.kill_ring.back().cloned() {
+            self.insert(&killed);
+        }
+    }
+
+    // Cuts part of the line into the kill ring (or just deletes it, if it's a secret)
+    fn kill(&mut self, range: Range<usize>) {
+        let killed = self.current_input.drain(range).collect::<String>();
+        if killed.is_empty() || self.secret {
+            return;
+        }
+        if self.kill_ring.len() == KILL_RING_SIZE {
+            self.kill_ring.pop_front();
+        }
+        self.kill_ring.push_back(killed);
+    }
+
+    /// Pastes text at the cursor. Each line break "presses Enter", so the lines finished that way
+    /// are returned to be run.
+    fn paste(&mut self, text: &str) -> Vec<String> {
+        let mut lines = text.split('\n').map(|line| line.trim_end_matches('\r'));
+        let last = lines.next_back().unwrap_or_default();
+        let entered = lines
+            .filter_map(|line| {
+                self.insert(line);
+                self.enter()
+            })
+            .collect();
+        self.insert(last);
+        entered
+    }
+
+    /// Tab-completes the word behind the cursor
+    fn complete(&mut self, context: &CommandContext) {
+        if let Some((range, completed)) =
+            completion::complete(&self.current_input, self.cursor_location, context)
+        {
+            self.cursor_location = range.start + completed.len();
+            self.current_input.replace_range(range, &completed);
+        }
+    }
+
+    /// Takes the current line, leaving the cursor ready for new input
+    fn take_input(&mut self) -> String {
+        self.cursor_location = 0;
+        self.undo_edits.clear();
+        self.redo_edits.clear();
+        self.typing = false;
+        self.vi = ViState::default();
+        std::mem::take(&mut self.current_input)
+    }
+
+    /// The line and cursor as they are, to pass to `record_edit` after a key is handled
+    fn snapshot(&self) -> (String, usize) {
+        (self.current_input.clone(), self.cursor_location)
+    }
+
+    /// Makes whatever a key did to the line undoable. `typed` is the text a typing key inserted:
+    /// typing runs together into one undo step until a space.
+    fn record_edit(&mut self, before: (String, usize), typed: Option<&str>) {
+        let typing = typed.is_some_and(|text| !text.ends_with(char::is_whitespace));
+        if before.0 == self.current_input {
+            // Moving around starts a new step
+            self.typing &= typing;
+            return;
+        }
+        if !(self.typing && typed.is_some()) {
+            if self.undo_edits.len() == EDIT_UNDO_LIMIT {
+                self.undo_edits.pop_front();
+            }
+            self.undo_edits.push_back(before);
+        }
+        self.redo_edits.clear();
+        self.typing = typing;
+    }
+
+    /// Puts the line back the way it was before the last edit (Ctrl+Z)
+    fn undo(&mut self) {
+        if let Some(before) = self.undo_edits.pop_back() {
+            let after = std::mem::replace(&mut self.current_input, before.0);
+            self.redo_edits.push((after, self.cursor_location));
+            self.cursor_location = before.1;
+        }
+        self.typing = false;
+    }
+
+    /// Makes the last undone edit again (Ctrl+Shift+Z)
+    fn redo(&mut self) {
+        if let Some(after) = self.redo_edits.pop() {
+            let before = std::mem::replace(&mut self.current_input, after.0);
+            self.undo_edits.push_back((before, self.cursor_location));
+            self.cursor_location = after.1;
+        }
+        self.typing = false;
+    }
+
+    /// Presses Enter. A line ending with `\` is held back to be continued on the next one;
+    /// otherwise the whole input (every continued line, joined by line breaks) is returned to run.
+    fn enter(&mut self) -> Option<String> {
+        let line = self.take_input();
+        if let Some(line) = line.strip_suffix('\\') {
+            self.continued.push(line.to_string());
+            return None;
+        }
+        self.continued.push(line);
+        Some(std::mem::take(&mut self.continued).join("\n"))
+    }
+
+    /// Takes all of the input, continued lines included, without running it
+    fn abandon(&mut self) -> String {
+        let line = self.take_input();
+        self.continued.push(line);
+        std::mem::take(&mut self.continued).join("\n")
+    }
 }
 
 #[derive(Component)]
 struct TerminalHistory;
 
+/// The window the terminal is shown in, so it only takes keyboard input from that window.
+/// `None` means the primary window.
+#[derive(Resource, Debug, Default)]
+pub struct TerminalWindow(pub Option<Entity>);
+
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq, States)]
 enum TerminalState {
     #[default]
@@ -60,22 +397,69 @@ fn terminal_cursor(terminal_assets: &TerminalAssets) -> impl Bundle {
             ..default()
         },
         TerminalCursor::default(),
-        Text::new(TERMINAL_CURSOR),
+        InputScroll::default(),
+        Text::default(),
         terminal_font(terminal_assets),
     )
 }
 
+/// The lines of a (possibly continued) input, with their prompts
+fn input_lines(input: &str, prompt: &str) -> impl Iterator<Item = String> {
+    input.split('\n').enumerate().map(move |(i, line)| {
+        let prompt = if i == 0 { prompt } else { CONTINUATION_PROMPT };
+        format!("{prompt}{line}")
+    })
+}
+
 // Helper for creating terminal history
 fn terminal_history(
     input: &str,
+    prompt: &str,
     output: &[String],
+    timestamp: Option<&str>,
     terminal_assets: &TerminalAssets,
 ) -> impl Bundle {
+    let lines = input_lines(input, prompt)
+        .chain(output.iter().cloned())
+        .collect::<Vec<String>>();
+    history_entry(&lines, timestamp, terminal_assets)
+}
+
+/// A history entry of the given lines, as they are
+fn history_entry(
+    lines: &[String],
+    timestamp: Option<&str>,
+    terminal_assets: &TerminalAssets,
+) -> impl Bundle + use<> {
+    // Every line gets its own (dimmed) timestamp, so they're spans rather than one string
+    let stamp = timestamp.unwrap_or_default();
+    let font = terminal_font(terminal_assets);
+    let spans = lines
+        .iter()
+        .cloned()
+        .enumerate()
+        .flat_map(|(i, line)| {
+            let newline = if i == 0 { "" } else { "\n" };
+            [
+                (
+                    TextSpan::new(format!("{newline}{stamp}")),
+                    font.clone(),
+                    TextColor(TIMESTAMP_TEXT),
+                ),
+                (TextSpan::new(line), font.clone(), TextColor::WHITE),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let plain_text = lines.join("\n");
+
     (
         Node {
             width: Val::Percent(100.0),
             ..default()
         },
+        BackgroundColor(Color::NONE),
+        HistoryEntry(plain_text),
         Pickable {
             should_block_lower: false,
             ..default()
@@ -85,13 +469,9 @@ fn terminal_history(
                 should_block_lower: false,
                 ..default()
             },
-            Text::new(format!(
-                "{}{}\n{}",
-                TERMINAL_CURSOR,
-                input,
-                output.join("\n")
-            )),
-            terminal_font(terminal_assets),
+            Text::default(),
+            font,
+            Children::spawn(SpawnIter(spans.into_iter())),
         )],
     )
 }
@@ -145,136 +525,402 @@ pub fn terminal(terminal_assets: &TerminalAssets) -> impl Bundle {
     )
 }
 
+/// Runs a line of input and adds it (and its output) to the terminal's history
+fn submit_line(
+    commands: &mut Commands,
+    terminal_history_entity: Entity,
+    input_raw: &str,
+    timestamp: Option<&str>,
+    context: &mut CommandContext,
+    terminal_assets: &TerminalAssets,
+) -> CommandOutput {
+    // Answers to a command's question aren't commands, so they don't go in macros
+    let answering = context.prompt.0.is_some();
+    let masked = context
+        .prompt
+        .0
+        .as_ref()
+        .is_some_and(|prompt| prompt.masked);
+    // The line shows with the prompt it was typed at, even if running it changes the prompt
+    let prompt_shown = context.terminal_prompt.render();
+    let output = command::run_line(input_raw, context).unwrap_or_else(|err| {
+        error!("Command line {input_raw:?} failed: {err}");
+        CommandOutput {
+            lines: vec![format!("[internal error] {err}")],
+            success: false,
+            clear: false,
+            restart: false,
+        }
+    });
+    // Macro commands aren't part of the macro itself
+    if !answering && command::is_recordable(input_raw) {
+        context.macros.record(input_raw);
+    }
+
+    // Show the input and output as history (keeping secrets out of it)
+    let input_shown = if masked {
+        mask(input_raw)
+    } else {
+        input_raw.to_string()
+    };
+    crash::remember(&input_shown);
+    // A line that just cleared the screen leaves nothing behind
+    if output.clear {
+        commands
+            .entity(terminal_history_entity)
+            .despawn_related::<Children>();
+    }
+    if !output.clear || !output.lines.is_empty() {
+        commands
+            .entity(terminal_history_entity)
+            .with_child(terminal_history(
+                &input_shown,
+                &prompt_shown,
+                &output.lines,
+                timestamp,
+                terminal_assets,
+            ));
+    }
+
+    output
+}
+
+/// Scrolls the terminal down to the newest history entry
+fn scroll_to_latest(
+    terminal_container_node: &ComputedNode,
+    terminal_container_scroll: &mut ScrollPosition,
+    output_lines: usize,
+) {
+    let total_history_newlines = output_lines as f32 + 2.0; // 2 is from input and the spacing between
+    // Layout sizes are in physical pixels, but scroll offsets (like LINE_HEIGHT) are logical
+    let content_height =
+        terminal_container_node.content_size().y * terminal_container_node.inverse_scale_factor();
+    terminal_container_scroll.offset_y = content_height + LINE_HEIGHT * total_history_newlines;
+}
+
+/// The resources the player's commands read and change
+#[derive(SystemParam)]
+struct CommandState<'w> {
+    notes: ResMut<'w, Notes>,
+    macros: ResMut<'w, Macros>,
+    undo: ResMut<'w, UndoHistory>,
+    timestamps: ResMut<'w, TerminalTimestamps>,
+    last_status: ResMut<'w, LastStatus>,
+    prompt: ResMut<'w, PendingPrompt>,
+    jukebox: ResMut<'w, Jukebox>,
+    edit_mode: ResMut<'w, TerminalEditMode>,
+    self_test: ResMut<'w, SelfTest>,
+    level_graph: LevelGraph<'w>,
+    sandbox: Res<'w, Sandbox>,
+    family_friendly: Res<'w, FamilyFriendly>,
+    text: Res<'w, TerminalText>,
+    terminal_prompt: ResMut<'w, TerminalPrompt>,
+    wiki: ResMut<'w, Wiki>,
+    #[cfg(feature = "analytics")]
+    commands_ran: EventWriter<'w, CommandsRan>,
+}
+
+impl CommandState<'_> {
+    fn context(&mut self) -> CommandContext<'_> {
+        CommandContext {
+            notes: &mut self.notes,
+            macros: &mut self.macros,
+            undo: &mut self.undo,
+            timestamps: &mut self.timestamps,
+            last_status: &mut self.last_status,
+            prompt: &mut self.prompt,
+            jukebox: &mut self.jukebox,
+            edit_mode: &mut self.edit_mode,
+            self_test: &mut self.self_test,
+            graph: self.level_graph.get(),
+            sandbox: self.sandbox.0,
+            family_friendly: self.family_friendly.0,
+            text: &self.text,
+            terminal_prompt: &mut self.terminal_prompt,
+            wiki: &mut self.wiki,
+        }
+    }
+}
+
+/// The current level's network (on its own, since `CommandState` can't hold any more)
+#[derive(SystemParam)]
+struct LevelGraph<'w> {
+    level_assets: Res<'w, LevelAssets>,
+    graphs: Res<'w, Assets<NetworkGraph>>,
+}
+
+impl LevelGraph<'_> {
+    fn get(&self) -> Option<&NetworkGraph> {
+        self.graphs.get(&self.level_assets.graph)
+    }
+}
+
 /// Handles catching and handling keyboard inputs
 /// Mimicking a real terminal as best I can.
 fn terminal_input(
     mut commands: Commands,
-    mut input_event_reader: EventReader<KeyboardInput>,
+    mut terminal_keys: TerminalKeys,
     terminal_assets: Res<TerminalAssets>,
     mut terminal_container_query: Query<
-        (&mut ComputedNode, &mut ScrollPosition),
+        (&ComputedNode, &mut ScrollPosition, &Children),
         With<TerminalContainer>,
     >,
-    mut terminal_cursor_query: Query<&mut TerminalCursor>,
-    mut terminal_history_entity_query: Query<Entity, With<TerminalHistory>>,
+    mut terminal_cursor_query: Query<
+        (Entity, &mut TerminalCursor, &ChildOf),
+        With<TerminalFocused>,
+    >,
+    terminal_history_query: Query<(), With<TerminalHistory>>,
+    mut command_state: CommandState,
+    run_clock: Res<RunClock>,
+    time: Res<Time>,
+    terminal_window: Res<TerminalWindow>,
+    speech: Res<TerminalSpeech>,
+    mut babbles: ResMut<Assets<Babble>>,
+    mut haptic_pulses: EventWriter<HapticPulse>,
+    primary_window_query: Query<Entity, With<PrimaryWindow>>,
+    chords: TerminalChords,
+    mut selection: ResMut<HistorySelection>,
 ) {
-    let Ok((terminal_container_node, mut terminal_container_scroll)) =
-        terminal_container_query.single_mut()
-    else {
+    // Keyboard input goes to the focused terminal, and nowhere while none is
+    let mut terminal_cursors = terminal_cursor_query.iter_mut();
+    let Some((cursor_entity, mut terminal_cursor, cursor_parent)) = terminal_cursors.next() else {
+        terminal_keys.clear();
         return;
     };
+    if terminal_cursors.next().is_some() {
+        warn_once!("More than one terminal is focused; only the first one gets input");
+    }
 
-    let Ok(mut terminal_cursor) = terminal_cursor_query.single_mut() else {
+    // The cursor lives in the container, next to the history
+    let Ok((terminal_container_node, mut terminal_container_scroll, terminal_container_children)) =
+        terminal_container_query.get_mut(cursor_parent.parent())
+    else {
+        error!("Terminal cursor isn't inside a terminal container");
         return;
     };
 
-    let Ok(terminal_history_entity) = terminal_history_entity_query.single_mut() else {
+    let Some(&terminal_history_entity) = terminal_container_children
+        .into_iter()
+        .find(|&&child| terminal_history_query.contains(child))
+    else {
+        error!("Terminal container has no history");
         return;
     };
 
-    for event in input_event_reader.read() {
+    let input_window = terminal_window.0.or(primary_window_query.single().ok());
+
+    for event in terminal_keys.read() {
+        // Keys typed into other windows aren't meant for us.
+        if input_window.is_some_and(|window| window != event.window) {
+            continue;
+        }
+
         // We only care about button presses right now.
         if event.state == ButtonState::Released {
             continue;
         }
 
-        // Play a sound
-        let rng = &mut rand::thread_rng();
-        let random_click = terminal_assets.clicks.choose(rng).unwrap().clone();
-        commands.spawn(sound_effect(random_click));
-
-        // Execute command
-        if event.key_code == KeyCode::Enter {
-            // Parse input
-            let input_raw = terminal_cursor.current_input.clone();
-            let input = terminal_cursor
-                .current_input
-                .split_whitespace()
-                .map(|s| s.trim().to_string())
-                .collect::<Vec<String>>();
-
-            // Build command (or just do a noop if there is no meaningful input)
-            let command = if input.is_empty() {
-                Command::Noop
-            } else {
-                Command::parse(&input[0])
-            };
-
-            let output = command.run(match command {
-                Command::Invalid => &input,
-                Command::Noop => &input,
-                _ => &input[1..],
-            });
-
-            // Show the input and output as history
-            commands
-                .entity(terminal_history_entity)
-                .with_child(terminal_history(&input_raw, &output, &terminal_assets));
-
-            // Reset cursor to except new input
-            terminal_cursor.current_input = String::new();
-            terminal_cursor.cursor_location = 0;
-
-            // Scroll to input
-            let total_history_newlines = output.len() as f32 + 2.0; // 2 is from input and the spacing between
-            let content_height = terminal_container_node.content_size().y;
-            let container_height = terminal_container_node.size().y - LINE_HEIGHT;
-
-            if container_height - LINE_HEIGHT < container_height {
-                terminal_container_scroll.offset_y =
-                    content_height + (LINE_HEIGHT * total_history_newlines)
+        let chord = chords.chord(event.key_code);
+        let action = chords.action(chord);
+        // While searching, keys change what's searched for; anything else takes the match
+        if terminal_cursor.search.is_some() {
+            let before = terminal_cursor.snapshot();
+            match action {
+                Some(EditAction::ReverseSearch) => {
+                    terminal_cursor.search_older();
+                    continue;
+                }
+                Some(EditAction::DeleteBackward) => {
+                    terminal_cursor.search_backspace();
+                    continue;
+                }
+                Some(EditAction::Leave) => {
+                    terminal_cursor.finish_search();
+                    terminal_cursor.record_edit(before, None);
+                    continue;
+                }
+                // Gives up on the search, leaving the line as it was
+                Some(EditAction::Interrupt) => {
+                    terminal_cursor.search = None;
+                    continue;
+                }
+                None if !chord.is_shortcut(event.text.as_deref()) => {
+                    if let Some(text) = &event.text {
+                        terminal_cursor.search_type(text);
+                    }
+                    continue;
+                }
+                _ => {
+                    terminal_cursor.finish_search();
+                    terminal_cursor.record_edit(before, None);
+                }
             }
-
-            continue;
         }
-
-        let cursor_location = terminal_cursor.cursor_location;
-        let input_length = terminal_cursor.current_input.len();
-
-        // Backspace (delete character behind)
-        if event.key_code == KeyCode::Backspace {
-            // Can't delete if we're at the beginning
-            if cursor_location == 0 || input_length == 0 {
-                continue;
+        // Secrets typed in answer to a masked question mustn't be yanked back later
+        terminal_cursor.secret = command_state
+            .prompt
+            .0
+            .as_ref()
+            .is_some_and(|prompt| prompt.masked);
+        // Lines to run: the one being typed (Enter), or whole lines pasted in
+        let mut entered = Vec::new();
+        let before = terminal_cursor.snapshot();
+        let mut typed = None;
+        match action {
+            // Execute command
+            // (answers to questions are taken as typed, even with a `\` on the end)
+            Some(EditAction::Submit) if command_state.prompt.0.is_some() => {
+                entered.push(terminal_cursor.take_input());
             }
-
-            // If at the end, truncate instead
-            if cursor_location == input_length {
-                terminal_cursor.current_input.truncate(input_length - 1);
-                terminal_cursor.cursor_location -= 1;
-                continue;
+            Some(EditAction::Submit) => entered.extend(terminal_cursor.enter()),
+            Some(EditAction::Paste) => {
+                if let Some(text) = clipboard::paste() {
+                    entered = terminal_cursor.paste(&text);
+                }
+            }
+            Some(EditAction::DeleteWordBackward) => terminal_cursor.delete_word_backward(),
+            Some(EditAction::DeleteBackward) => terminal_cursor.delete_backward(),
+            Some(EditAction::DeleteForward) => terminal_cursor.delete_forward(),
+            Some(EditAction::MoveWordLeft) => terminal_cursor.move_word_left(),
+            Some(EditAction::MoveWordRight) => terminal_cursor.move_word_right(),
+            Some(EditAction::MoveLeft) => terminal_cursor.move_left(),
+            Some(EditAction::MoveRight) => terminal_cursor.move_right(),
+            Some(EditAction::MoveHome) => terminal_cursor.move_home(),
+            Some(EditAction::MoveEnd) => terminal_cursor.move_end(),
+            Some(EditAction::KillToStart) => terminal_cursor.kill_to_start(),
+            Some(EditAction::KillToEnd) => terminal_cursor.kill_to_end(),
+            Some(EditAction::Yank) => terminal_cursor.yank(),
+            Some(EditAction::Leave)
+                if *command_state.edit_mode == TerminalEditMode::Vi
+                    && terminal_cursor.vi_uses_escape() =>
+            {
+                terminal_cursor.vi_normal_mode();
+            }
+            // Lets go of the keyboard, for the rest of the screen (another Esc pauses, and Enter
+            // or Tab takes it back)
+            Some(EditAction::Leave) => {
+                commands.entity(cursor_entity).remove::<TerminalFocused>();
+            }
+            Some(EditAction::ReverseSearch) if command_state.prompt.0.is_none() => {
+                terminal_cursor.search_older();
+            }
+            Some(EditAction::ToggleOverwrite) => {
+                terminal_cursor.overwrite = !terminal_cursor.overwrite;
+            }
+            Some(EditAction::Undo) => terminal_cursor.undo(),
+            Some(EditAction::Redo) => terminal_cursor.redo(),
+            // Clears the screen, keeping the line being typed
+            Some(EditAction::ClearScreen) => {
+                commands
+                    .entity(terminal_history_entity)
+                    .despawn_related::<Children>();
+                terminal_container_scroll.offset_y = 0.0;
+            }
+            // Copies selected history
+            Some(EditAction::Interrupt) if !selection.text.is_empty() => {
+                clipboard::copy(&selection.text);
+                selection.clear();
+            }
+            // Abandons the line, and any question waiting on it
+            Some(EditAction::Interrupt) => {
+                let input_raw = terminal_cursor.abandon();
+                let masked = command_state
+                    .prompt
+                    .0
+                    .take()
+                    .is_some_and(|prompt| prompt.masked);
+                let input_shown = if masked { mask(&input_raw) } else { input_raw };
+                let timestamp = command_state.timestamps.0.then(|| run_clock.stamp(&time));
+                commands
+                    .entity(terminal_history_entity)
+                    .with_child(terminal_history(
+                        &format!("{input_shown}^C"),
+                        &command_state.terminal_prompt.render(),
+                        &[],
+                        timestamp.as_deref(),
+                        &terminal_assets,
+                    ));
+                scroll_to_latest(terminal_container_node, &mut terminal_container_scroll, 0);
+            }
+            // Answers to questions aren't commands
+            Some(EditAction::Complete) if command_state.prompt.0.is_none() => {
+                terminal_cursor.complete(&command_state.context());
+            }
+            // TODO control characters
+            _ if chord.is_shortcut(event.text.as_deref()) => {}
+            // Keys are vi commands in normal mode
+            _ if terminal_cursor.vi.normal && *command_state.edit_mode == TerminalEditMode::Vi => {
+                for key in event.text.iter().flat_map(|text| text.chars()) {
+                    terminal_cursor.vi_key(key);
+                }
+            }
+            _ => {
+                if let Some(text) = &event.text {
+                    if terminal_cursor.overwrite {
+                        terminal_cursor.overwrite(text);
+                    } else {
+                        terminal_cursor.insert(text);
+                    }
+                    typed = Some(text.as_str());
+                }
             }
-
-            // Remove from location
-            terminal_cursor.current_input.remove(cursor_location);
-            terminal_cursor.cursor_location -= 1;
-
-            continue;
+        }
+        // Undo and redo aren't edits of their own, and a line that's been taken is done with
+        if !matches!(
+            action,
+            Some(EditAction::Submit | EditAction::Undo | EditAction::Redo | EditAction::Interrupt)
+        ) {
+            terminal_cursor.record_edit(before, typed);
         }
 
-        // Del (delete character ahead)
-        if event.key_code == KeyCode::Delete {
-            // Can't delete if there's nothing to delete
-            if input_length == 0 {
-                continue;
+        for input_raw in entered {
+            let answering = command_state.prompt.0.is_some();
+            // Answers to questions aren't worth searching for (and could be secrets)
+            if !answering {
+                terminal_cursor.remember_line(&input_raw);
             }
+            let timestamp = command_state.timestamps.0.then(|| run_clock.stamp(&time));
+            let output = submit_line(
+                &mut commands,
+                terminal_history_entity,
+                &input_raw,
+                timestamp.as_deref(),
+                &mut command_state.context(),
+                &terminal_assets,
+            );
 
-            // At the end of input, so don't do anything
-            if cursor_location == input_length {
-                continue;
+            haptic_pulses.write(HapticPulse::TICK);
+            // Answers to questions aren't commands (and could be secrets)
+            #[cfg(feature = "analytics")]
+            if !answering {
+                command_state
+                    .commands_ran
+                    .write(CommandsRan::new(&input_raw, output.success));
             }
-        }
 
-        // TODO control characters + tab completion
+            // "Speak" the output
+            if speech.0 {
+                let line = Babble::new(&output.lines.join(" "));
+                if !line.is_empty() {
+                    commands.spawn(babble(babbles.add(line)));
+                }
+            }
 
-        let Some(text) = &event.text else {
-            return;
-        };
+            if output.clear {
+                terminal_container_scroll.offset_y = 0.0;
+            } else {
+                scroll_to_latest(
+                    terminal_container_node,
+                    &mut terminal_container_scroll,
+                    output.lines.len(),
+                );
+            }
 
-        terminal_cursor
-            .current_input
-            .insert_str(cursor_location, text.as_str());
-        terminal_cursor.cursor_location += 1;
+            // Going back through the loading screen starts the level over, fresh
+            if output.restart {
+                commands.set_state(Screen::Loading);
+            }
+        }
     }
 }
 
@@ -300,32 +946,488 @@ fn terminal_scrolling(
     }
 }
 
+/// Shows a finished self-test's report in the terminal
+fn print_self_test_report(
+    mut commands: Commands,
+    mut self_test: ResMut<SelfTest>,
+    terminal_history: Single<Entity, With<TerminalHistory>>,
+    terminal_assets: Res<TerminalAssets>,
+) {
+    if let Some(report) = self_test.report.take() {
+        commands.entity(*terminal_history).with_child(history_entry(
+            &report.lines,
+            None,
+            &terminal_assets,
+        ));
+    }
+}
+
 // Handles displaying text input
 fn terminal_text(
-    mut terminal_query: Query<(&mut TerminalCursor, &mut Text), Changed<TerminalCursor>>,
+    mut terminal_query: Query<(Ref<TerminalCursor>, &mut InputScroll, &mut Text, &ChildOf)>,
+    terminal_container_query: Query<Ref<ComputedNode>, With<TerminalContainer>>,
+    prompt: Res<PendingPrompt>,
+    terminal_prompt: Res<TerminalPrompt>,
+    blink: Res<CursorBlink>,
 ) {
-    let Ok((terminal, mut text)) = terminal_query.single_mut() else {
+    let masked = prompt.0.as_ref().is_some_and(|prompt| prompt.masked);
+    let shown_prompt = terminal_prompt.render();
+    for (terminal, mut scroll, mut text, cursor_parent) in &mut terminal_query {
+        let container_node = terminal_container_query.get(cursor_parent.parent()).ok();
+        let resized = container_node
+            .as_ref()
+            .is_some_and(|container_node| container_node.is_changed());
+        if !terminal.is_changed()
+            && !blink.is_changed()
+            && !resized
+            && !terminal_prompt.is_changed()
+        {
+            continue;
+        }
+
+        // A search shows what's searched for and what it found, in readline's words
+        if let Some(search) = &terminal.search {
+            let found = terminal
+                .search_match()
+                .unwrap_or_default()
+                .replace('\n', " ");
+            let cursor = if blink.visible { CURSOR_BLOCK } else { ' ' };
+            text.0 = format!("(reverse-i-search)`{}': {found}{cursor}", search.query);
+            continue;
+        }
+
+        let input = if masked {
+            mask(&terminal.current_input)
+        } else {
+            terminal.current_input.clone()
+        };
+        // Masking keeps one char per character, so the cursor is at the same character either way
+        let cursor = terminal.current_input[..terminal.cursor_location]
+            .graphemes(true)
+            .count();
+        let input = input.graphemes(true).collect::<Vec<_>>();
+
+        // Lines already entered with a `\` stay above the one being typed
+        text.0 = String::new();
+        for (i, line) in terminal.continued.iter().enumerate() {
+            let line = if masked { mask(line) } else { line.clone() };
+            let prompt = if i == 0 {
+                shown_prompt.as_str()
+            } else {
+                CONTINUATION_PROMPT
+            };
+            text.0.push_str(&format!("{prompt}{line}\\\n"));
+        }
+        let line_prompt = if terminal.continued.is_empty() {
+            shown_prompt.as_str()
+        } else {
+            CONTINUATION_PROMPT
+        };
+        text.0.push_str(line_prompt);
+
+        // A line too long for the terminal scrolls sideways to keep the cursor in view (before
+        // layout, the width isn't known yet, so nothing's clipped)
+        let columns = container_node
+            .map(|node| (node.size().x * node.inverse_scale_factor() / CHAR_WIDTH) as usize)
+            .filter(|&columns| columns > 0)
+            .map_or(usize::MAX, |columns| {
+                columns.saturating_sub(line_prompt.chars().count())
+            });
+        let view = scroll.view(&input, cursor, columns);
+        let (input, cursor) = (view.graphemes, view.cursor);
+        if view.clipped_left {
+            text.0.push('<');
+        }
+        text.0.extend(input.iter().take(cursor).copied());
+        if masked {
+            text.0.push_str(&mask(&terminal.preedit));
+        } else {
+            text.0.push_str(&terminal.preedit);
+        }
+        // The block covers the character under the cursor (or the space after the line)
+        let under_cursor = input.get(cursor);
+        // A steady block means vi normal mode, and an underscore means overwrite mode
+        let cursor_shape = if terminal.overwrite {
+            CURSOR_UNDERSCORE
+        } else {
+            CURSOR_BLOCK
+        };
+        match (blink.visible || terminal.vi.normal, under_cursor) {
+            (true, _) => text.0.push(cursor_shape),
+            (false, Some(grapheme)) => text.0.push_str(grapheme),
+            (false, None) => text.0.push(' '),
+        }
+        text.0.extend(input.iter().skip(cursor + 1).copied());
+        if view.clipped_right {
+            text.0.push('>');
+        }
+    }
+}
+
+/// Where the terminal reads the keyboard. Other Esc handlers go before it, so they see the
+/// terminal as it was when the key was pressed.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TerminalInput;
+
+/// Whether the cursor block is showing, flipping on a timer like a VT terminal's
+#[derive(Resource, Debug)]
+struct CursorBlink {
+    timer: Timer,
+    visible: bool,
+}
+
+impl Default for CursorBlink {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(CURSOR_BLINK_SECS, TimerMode::Repeating),
+            visible: true,
+        }
+    }
+}
+
+fn blink_cursor(
+    mut blink: ResMut<CursorBlink>,
+    terminal_query: Query<(), Changed<TerminalCursor>>,
+    time: Res<Time>,
+) {
+    // The cursor stays solid while typing
+    if !terminal_query.is_empty() {
+        if !blink.visible || blink.timer.elapsed_secs() > 0.0 {
+            *blink = CursorBlink::default();
+        }
         return;
-    };
-    text.0 = String::new();
+    }
 
-    text.0.push_str(TERMINAL_CURSOR);
-    text.0.push_str(&terminal.current_input);
+    if blink
+        .bypass_change_detection()
+        .timer
+        .tick(time.delta())
+        .just_finished()
+    {
+        blink.visible = !blink.visible;
+    }
+}
+
+// Hides what's typed (passwords and the like), keeping only its length
+fn mask(input: &str) -> String {
+    "*".repeat(input.graphemes(true).count())
 }
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         (
-            terminal_input.run_if(in_state(TerminalState::Ready)),
+            (key_repeat::repeat_held_keys, terminal_input)
+                .chain()
+                .in_set(TerminalInput)
+                // Observers only watch
+                .run_if(in_state(TerminalState::Ready).and(not(observing))),
             terminal_scrolling,
-            terminal_text,
+            print_self_test_report,
         )
             .run_if(in_state(Screen::Gameplay)),
     );
+    // Terminals can show up outside of gameplay too (e.g. the title screen's attract mode)
+    app.add_systems(Update, (blink_cursor, terminal_text).chain());
 
     app.init_state::<TerminalState>();
+    app.init_resource::<TerminalWindow>();
+    app.init_resource::<CursorBlink>();
+    app.register_type::<LastStatus>();
+    app.init_resource::<LastStatus>();
+    #[cfg(feature = "analytics")]
+    app.add_event::<CommandsRan>();
+
+    app.add_plugins((
+        attract::plugin,
+        captions::plugin,
+        clicks::plugin,
+        focus::plugin,
+        macros::plugin,
+        notes::plugin,
+        prompt::plugin,
+        responses::plugin,
+        selection::plugin,
+        shell_prompt::plugin,
+        speech::plugin,
+        timestamps::plugin,
+        undo::plugin,
+        wiki::plugin,
+    ));
+    // Line editing
+    app.add_plugins((ime::plugin, key_repeat::plugin, keymap::plugin, vi::plugin));
 
     app.register_type::<TerminalAssets>();
     app.load_resource::<TerminalAssets>();
 }
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[derive(Clone, Debug)]
+    enum EditKey {
+        Type(String),
+        Backspace,
+        Delete,
+        Left,
+        Right,
+        Home,
+        End,
+    }
+
+    fn edit_key() -> impl Strategy<Value = EditKey> {
+        prop_oneof![
+            // Mostly short bursts of (possibly multi-byte, possibly multi-char) characters, plus
+            // the odd control character
+            prop::collection::vec(
+                prop::sample::select(vec!["a", " ", "é", "e\u{301}", "日", "😀", "👍🏽"]),
+                1..=3
+            )
+            .prop_map(|text| EditKey::Type(text.concat())),
+            "[\\x00-\\x1f]".prop_map(EditKey::Type),
+            Just(EditKey::Backspace),
+            Just(EditKey::Delete),
+            Just(EditKey::Left),
+            Just(EditKey::Right),
+            Just(EditKey::Home),
+            Just(EditKey::End),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_cursor_editing_matches_model(keys in prop::collection::vec(edit_key(), 0..64)) {
+            let mut cursor = TerminalCursor::default();
+            // Reference model: the line as characters, with the cursor as a character index
+            let mut model: Vec<String> = Vec::new();
+            let mut model_cursor = 0;
+
+            for key in keys {
+                match key {
+                    EditKey::Type(text) => {
+                        cursor.insert(&text);
+                        let text = text.chars().filter(|c| !c.is_control()).collect::<String>();
+                        for grapheme in text.graphemes(true) {
+                            model.insert(model_cursor, grapheme.to_string());
+                            model_cursor += 1;
+                        }
+                    }
+                    EditKey::Backspace => {
+                        cursor.delete_backward();
+                        if model_cursor > 0 {
+                            model_cursor -= 1;
+                            model.remove(model_cursor);
+                        }
+                    }
+                    EditKey::Delete => {
+                        cursor.delete_forward();
+                        if model_cursor < model.len() {
+                            model.remove(model_cursor);
+                        }
+                    }
+                    EditKey::Left => {
+                        cursor.move_left();
+                        model_cursor = model_cursor.saturating_sub(1);
+                    }
+                    EditKey::Right => {
+                        cursor.move_right();
+                        model_cursor = (model_cursor + 1).min(model.len());
+                    }
+                    EditKey::Home => {
+                        cursor.move_home();
+                        model_cursor = 0;
+                    }
+                    EditKey::End => {
+                        cursor.move_end();
+                        model_cursor = model.len();
+                    }
+                }
+
+                prop_assert!(cursor.cursor_location <= cursor.current_input.len());
+                prop_assert!(cursor.current_input.is_char_boundary(cursor.cursor_location));
+                prop_assert_eq!(&cursor.current_input, &model.concat());
+                prop_assert_eq!(
+                    cursor.current_input[..cursor.cursor_location].graphemes(true).count(),
+                    model_cursor
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_word_editing() {
+        let mut cursor = TerminalCursor::default();
+        cursor.insert("note add  r01");
+        cursor.move_word_left();
+        assert_eq!(cursor.cursor_location, 10);
+        cursor.move_word_left();
+        assert_eq!(cursor.cursor_location, 5);
+        cursor.move_word_right();
+        assert_eq!(cursor.cursor_location, 8);
+
+        // Deletes "add", leaving the spaces around it
+        cursor.delete_word_backward();
+        assert_eq!(cursor.current_input, "note   r01");
+        assert_eq!(cursor.cursor_location, 5);
+        cursor.move_end();
+        cursor.delete_word_backward();
+        assert_eq!(cursor.current_input, "note   ");
+    }
+
+    #[test]
+    fn test_paste_enters_each_line() {
+        let mut cursor = TerminalCursor::default();
+        cursor.insert("ls ");
+        assert!(cursor.paste("r01").is_empty());
+        assert_eq!(cursor.current_input, "ls r01");
+
+        // Every line break is an Enter; whatever's after the last one is left to edit
+        let entered = cursor.paste("\r\nnote a\r\nnote b\nnote");
+        assert_eq!(entered, vec!["ls r01", "note a", "note b"]);
+        assert_eq!(cursor.current_input, "note");
+        assert_eq!(cursor.cursor_location, 4);
+    }
+
+    #[test]
+    fn test_multi_char_characters() {
+        let mut cursor = TerminalCursor::default();
+        // An accent typed as its own combining char joins the `e` before it
+        cursor.insert("caf");
+        cursor.insert("e");
+        cursor.insert("\u{301}");
+        cursor.insert("👍🏽");
+        cursor.move_left();
+        assert_eq!(cursor.cursor_location, "cafe\u{301}".len());
+        cursor.delete_forward();
+        cursor.delete_backward();
+        assert_eq!(cursor.current_input, "caf");
+        assert_eq!(cursor.cursor_location, 3);
+    }
+
+    #[test]
+    fn test_overwrite() {
+        let mut cursor = TerminalCursor::default();
+        cursor.insert("ls r01");
+        cursor.move_home();
+        cursor.overwrite("cd");
+        assert_eq!(cursor.current_input, "cd r01");
+        assert_eq!(cursor.cursor_location, 2);
+
+        // Multi-byte characters are replaced whole, and the line grows past its end
+        cursor.current_input = "né".to_string();
+        cursor.cursor_location = 1;
+        cursor.overwrite("ot");
+        assert_eq!(cursor.current_input, "not");
+        assert_eq!(cursor.cursor_location, 3);
+    }
+
+    #[test]
+    fn test_backslash_continuation() {
+        let mut cursor = TerminalCursor::default();
+        cursor.insert("note one \\");
+        assert_eq!(cursor.enter(), None);
+        cursor.insert("two\\");
+        assert_eq!(cursor.enter(), None);
+        cursor.insert("three");
+        assert_eq!(cursor.enter().as_deref(), Some("note one \ntwo\nthree"));
+        assert!(cursor.continued.is_empty());
+
+        // Pasted lines continue the same way
+        let entered = cursor.paste("ls \\\nr01\nls\n");
+        assert_eq!(entered, vec!["ls \nr01", "ls"]);
+    }
+
+    #[test]
+    fn test_undo_redo_edits() {
+        fn type_text(cursor: &mut TerminalCursor, text: &str) {
+            for c in text.chars() {
+                let before = cursor.snapshot();
+                cursor.insert(&c.to_string());
+                cursor.record_edit(before, Some(&c.to_string()));
+            }
+        }
+
+        let mut cursor = TerminalCursor::default();
+        type_text(&mut cursor, "ls r01");
+        let before = cursor.snapshot();
+        cursor.delete_word_backward();
+        cursor.record_edit(before, None);
+        assert_eq!(cursor.current_input, "ls ");
+
+        // Each typed word is one step, and so is the deletion
+        cursor.undo();
+        assert_eq!(cursor.current_input, "ls r01");
+        cursor.undo();
+        assert_eq!(cursor.current_input, "ls ");
+        cursor.undo();
+        assert_eq!(cursor.current_input, "");
+        cursor.undo();
+        assert_eq!(cursor.current_input, "");
+
+        cursor.redo();
+        cursor.redo();
+        assert_eq!(cursor.current_input, "ls r01");
+        assert_eq!(cursor.cursor_location, 6);
+
+        // A new edit drops what was left to redo
+        type_text(&mut cursor, "!");
+        cursor.redo();
+        assert_eq!(cursor.current_input, "ls r01!");
+    }
+
+    #[test]
+    fn test_kill_and_yank() {
+        let mut cursor = TerminalCursor::default();
+        cursor.insert("ls r01 ws01");
+        cursor.move_word_left();
+        cursor.kill_to_end();
+        assert_eq!(cursor.current_input, "ls r01 ");
+        cursor.move_home();
+        cursor.kill_to_start();
+        assert_eq!(cursor.current_input, "ls r01 ");
+
+        // Nothing was killed the second time, so the last kill is still there to yank
+        cursor.move_end();
+        cursor.yank();
+        assert_eq!(cursor.current_input, "ls r01 ws01");
+        cursor.kill_to_start();
+        assert_eq!(cursor.current_input, "");
+        cursor.yank();
+        assert_eq!(cursor.current_input, "ls r01 ws01");
+        assert_eq!(cursor.cursor_location, cursor.current_input.len());
+
+        // Killing a secret deletes it without replacing what's there to yank
+        cursor.take_input();
+        cursor.secret = true;
+        cursor.insert("hunter2");
+        cursor.kill_to_start();
+        assert_eq!(cursor.current_input, "");
+        cursor.yank();
+        assert_eq!(cursor.current_input, "ls r01 ws01");
+    }
+
+    #[test]
+    fn test_take_input_resets_cursor() {
+        let mut cursor = TerminalCursor::default();
+        cursor.insert("ls r01");
+        assert_eq!(cursor.take_input(), "ls r01");
+        assert_eq!(cursor.current_input, "");
+        assert_eq!(cursor.cursor_location, 0);
+    }
+
+    #[test]
+    fn test_scroll_to_latest_on_a_scaled_display() {
+        // 400 physical pixels of history at 2x is 200 logical ones
+        let node = ComputedNode {
+            content_size: Vec2::new(300.0, 400.0),
+            inverse_scale_factor: 0.5,
+            ..default()
+        };
+        let mut scroll = ScrollPosition::default();
+        scroll_to_latest(&node, &mut scroll, 1);
+        assert_eq!(scroll.offset_y, 200.0 + LINE_HEIGHT * 3.0);
+    }
+}
@@ -0,0 +1,96 @@
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::{screens::Screen, theme::prelude::*};
+
+/// Free-form scratchpad the player keeps for the current run
+/// (passwords, suspicions, plans...).
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct Notes(pub Vec<String>);
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct NotesPanel;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct NotesPanelText;
+
+// Builds the notes panel (hidden until toggled) and the HUD button that toggles it
+pub fn notes_panel() -> impl Bundle {
+    (
+        Name::new("Notes HUD"),
+        Node {
+            align_items: AlignItems::End,
+            flex_direction: FlexDirection::Column,
+            position_type: PositionType::Absolute,
+            right: Px(10.0),
+            row_gap: Px(10.0),
+            top: Px(10.0),
+            ..default()
+        },
+        children![
+            widget::button_small("N", toggle_notes_panel),
+            (
+                Name::new("Notes Panel"),
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+                BorderColor(Color::WHITE),
+                Node {
+                    border: UiRect::all(Px(2.0)),
+                    display: Display::None,
+                    padding: UiRect::all(Px(5.0)),
+                    width: Px(300.0),
+                    ..default()
+                },
+                NotesPanel,
+                children![(widget::label(""), NotesPanelText)],
+            )
+        ],
+    )
+}
+
+fn toggle_notes_panel(
+    _: Trigger<Pointer<Click>>,
+    mut panel_query: Query<&mut Node, With<NotesPanel>>,
+) {
+    for mut node in &mut panel_query {
+        node.display = match node.display {
+            Display::None => Display::Flex,
+            _ => Display::None,
+        };
+    }
+}
+
+fn update_notes_panel(notes: Res<Notes>, mut text_query: Query<&mut Text, With<NotesPanelText>>) {
+    for mut text in &mut text_query {
+        text.0 = if notes.0.is_empty() {
+            "No notes yet.".to_string()
+        } else {
+            notes
+                .0
+                .iter()
+                .enumerate()
+                .map(|(i, note)| format!("{}. {note}", i + 1))
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+    }
+}
+
+// Every run starts with an empty scratchpad
+fn reset_notes(mut notes: ResMut<Notes>) {
+    notes.0.clear();
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Notes>();
+    app.register_type::<NotesPanel>();
+    app.register_type::<NotesPanelText>();
+    app.init_resource::<Notes>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), reset_notes);
+    app.add_systems(
+        Update,
+        update_notes_panel.run_if(in_state(Screen::Gameplay).and(resource_changed::<Notes>)),
+    );
+}
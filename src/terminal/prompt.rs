@@ -0,0 +1,43 @@
+//! Commands asking the player a question mid-way: the next line typed is the answer.
+
+use bevy::prelude::*;
+
+use crate::screens::Screen;
+
+/// What a command does once its question has been answered
+#[derive(Debug, Reflect)]
+pub enum PromptAction {
+    // Start recording over an existing macro with this name
+    OverwriteMacro(String),
+    // Export the map over the file already at this path
+    OverwriteMap(String),
+}
+
+#[derive(Debug, Reflect)]
+pub struct Prompt {
+    pub question: String,
+    // Whether the answer is secret (echoed as `*` and kept out of history)
+    pub masked: bool,
+    pub action: PromptAction,
+}
+
+/// The question the terminal is waiting on an answer to, if any.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct PendingPrompt(pub Option<Prompt>);
+
+/// Whether the player's answer was a yes
+pub fn is_yes(answer: &str) -> bool {
+    matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+}
+
+// Questions from a run shouldn't outlive it (or mask other terminals, like the attract mode's)
+fn clear_prompt(mut prompt: ResMut<PendingPrompt>) {
+    prompt.0 = None;
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PendingPrompt>();
+    app.init_resource::<PendingPrompt>();
+    app.add_systems(OnExit(Screen::Gameplay), clear_prompt);
+}
@@ -0,0 +1,233 @@
+//! The terminal's canned responses, in its usual snarky voice or a family-friendly one.
+//!
+//! Anything the terminal says that's meant to be cheeky should come from here, so the
+//! family-friendly setting catches it.
+//!
+//! The wording (and command help) can be rewritten in `assets/text/terminal.strings`, which is
+//! hot reloaded, so the tone can be tweaked while the game runs. Anything missing from that
+//! file (or the whole file) falls back to the built-in text below.
+
+use std::collections::HashMap;
+
+use bevy::{
+    asset::{AssetLoader, AsyncReadExt, LoadContext, io::Reader},
+    prelude::*,
+};
+use thiserror::Error;
+
+use super::command::Command;
+
+const TERMINAL_STRINGS_PATH: &str = "text/terminal.strings";
+
+/// Whether the terminal keeps its attitude to itself (for streams, classrooms...).
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct FamilyFriendly(pub bool);
+
+#[derive(Debug, Clone, Copy)]
+pub enum Response {
+    HelpOnHelp,
+    HelpOnNonsense,
+    HelpForgotCommands,
+    InvalidCommand,
+    NothingToList,
+    NowhereToGo,
+    NoSuchNote,
+    NoNotes,
+}
+
+impl Response {
+    /// What the response is called in `terminal.strings` (with `.family` on the end for the
+    /// family-friendly version)
+    fn key(self) -> &'static str {
+        match self {
+            Response::HelpOnHelp => "help_on_help",
+            Response::HelpOnNonsense => "help_on_nonsense",
+            Response::HelpForgotCommands => "help_forgot_commands",
+            Response::InvalidCommand => "invalid_command",
+            Response::NothingToList => "nothing_to_list",
+            Response::NowhereToGo => "nowhere_to_go",
+            Response::NoSuchNote => "no_such_note",
+            Response::NoNotes => "no_notes",
+        }
+    }
+
+    /// The built-in wording
+    pub fn text(self, family_friendly: bool) -> &'static str {
+        match (self, family_friendly) {
+            (Response::HelpOnHelp, false) => "Uh... You serious?",
+            (Response::HelpOnHelp, true) => "Shows help for a command.",
+            (Response::HelpOnNonsense, false) => {
+                "Man... I don't even know! What nonsense are you asking me?"
+            }
+            (Response::HelpOnNonsense, true) => "There's no command by that name.",
+            (Response::HelpForgotCommands, false) => "Lol, can't remember your own commands?",
+            (Response::HelpForgotCommands, true) => "Available commands:",
+            (Response::InvalidCommand, false) => {
+                "Invalid command, dummy (type ? if you already forgot your own scripts)"
+            }
+            (Response::InvalidCommand, true) => "Unknown command (type ? for a list of commands)",
+            (Response::NothingToList, false) => "Nothing to list. Are you even plugged in?",
+            (Response::NothingToList, true) => "Nothing to list. No network is loaded.",
+            (Response::NowhereToGo, false) => "Nowhere to go. Are you even plugged in?",
+            (Response::NowhereToGo, true) => "Nowhere to go. No network is loaded.",
+            (Response::NoSuchNote, false) => "Count much?",
+            (Response::NoSuchNote, true) => "Type \"notes\" to see them all.",
+            (Response::NoNotes, false) => "No notes yet. Memory like a goldfish, huh?",
+            (Response::NoNotes, true) => "No notes yet.",
+        }
+    }
+}
+
+/// `key = text` lines: responses and command help (`help.<command>`) in `terminal.strings`, or
+/// topics and their entries in `wiki.strings`
+#[derive(Asset, TypePath, Debug, Default)]
+pub struct TerminalStrings(pub(super) HashMap<String, String>);
+
+#[derive(Debug, Error)]
+pub enum TerminalStringsError {
+    #[error("Io Error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Line {0} isn't \"key = text\"")]
+    BadLine(usize),
+}
+
+impl TerminalStrings {
+    pub fn parse(source: &str) -> Result<Self, TerminalStringsError> {
+        let mut strings = HashMap::new();
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, text) = line
+                .split_once('=')
+                .ok_or(TerminalStringsError::BadLine(index + 1))?;
+            strings.insert(key.trim().to_string(), text.trim().to_string());
+        }
+        Ok(Self(strings))
+    }
+}
+
+#[derive(Default)]
+struct TerminalStringsLoader;
+
+impl AssetLoader for TerminalStringsLoader {
+    type Asset = TerminalStrings;
+    type Settings = ();
+    type Error = TerminalStringsError;
+
+    fn extensions(&self) -> &[&str] {
+        &["strings"]
+    }
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut source = String::new();
+        reader.read_to_string(&mut source).await?;
+        TerminalStrings::parse(&source)
+    }
+}
+
+/// The terminal's wording: whatever `terminal.strings` says, over the built-in text
+#[derive(Resource, Debug, Default)]
+pub struct TerminalText {
+    handle: Handle<TerminalStrings>,
+    strings: HashMap<String, String>,
+}
+
+impl TerminalText {
+    pub fn response(&self, response: Response, family_friendly: bool) -> &str {
+        let key = if family_friendly {
+            format!("{}.family", response.key())
+        } else {
+            response.key().to_string()
+        };
+        self.strings
+            .get(&key)
+            .map_or_else(|| response.text(family_friendly), String::as_str)
+    }
+
+    /// What `help <command>` says about a command
+    pub fn help(&self, command: &Command) -> Option<&str> {
+        let written = command
+            .name()
+            .and_then(|name| self.strings.get(&format!("help.{name}")));
+        written.map(String::as_str).or_else(|| command.help_text())
+    }
+}
+
+fn load_terminal_text(mut terminal_text: ResMut<TerminalText>, asset_server: Res<AssetServer>) {
+    terminal_text.handle = asset_server.load(TERMINAL_STRINGS_PATH);
+}
+
+// Picks up the file when it loads, and again every time it's saved
+fn update_terminal_text(
+    mut events: EventReader<AssetEvent<TerminalStrings>>,
+    mut terminal_text: ResMut<TerminalText>,
+    strings: Res<Assets<TerminalStrings>>,
+) {
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event
+        else {
+            continue;
+        };
+        if *id != terminal_text.handle.id() {
+            continue;
+        }
+        if let Some(loaded) = strings.get(*id) {
+            terminal_text.strings = loaded.0.clone();
+        }
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<FamilyFriendly>();
+    app.init_resource::<FamilyFriendly>();
+
+    app.init_asset::<TerminalStrings>();
+    app.init_asset_loader::<TerminalStringsLoader>();
+    app.init_resource::<TerminalText>();
+    app.add_systems(Startup, load_terminal_text);
+    app.add_systems(Update, update_terminal_text);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_written_text_overrides_built_in() {
+        let strings = TerminalStrings::parse(
+            "# Gentler\nno_notes = Nothing here yet!\nhelp.ls = Lists things.\n",
+        )
+        .unwrap();
+        let text = TerminalText {
+            strings: strings.0,
+            ..default()
+        };
+
+        assert_eq!(text.response(Response::NoNotes, false), "Nothing here yet!");
+        // Only the snarky version was rewritten
+        assert_eq!(text.response(Response::NoNotes, true), "No notes yet.");
+        assert_eq!(text.help(&Command::List), Some("Lists things."));
+        assert_eq!(text.help(&Command::Clear), Command::Clear.help_text());
+
+        assert!(matches!(
+            TerminalStrings::parse("no equals sign"),
+            Err(TerminalStringsError::BadLine(1))
+        ));
+    }
+
+    #[test]
+    fn test_shipped_strings_rewrite_nothing() {
+        // The built-in text is the wording; the shipped file only has examples of rewriting it
+        let shipped =
+            TerminalStrings::parse(include_str!("../../assets/text/terminal.strings")).unwrap();
+        assert!(shipped.0.is_empty());
+    }
+}
@@ -0,0 +1,110 @@
+//! Picking out history entries with the mouse (press, or drag across several) so they can be
+//! copied with Ctrl+C.
+
+use bevy::prelude::*;
+
+use super::TerminalHistory;
+
+/// #2a3f7a
+const SELECTION_BACKGROUND: Color = Color::srgb(0.165, 0.247, 0.478);
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<HistoryEntry>();
+    app.init_resource::<HistorySelection>();
+
+    app.add_observer(start_selection);
+    app.add_observer(extend_selection);
+    app.add_systems(Update, highlight_selection);
+}
+
+/// One submitted line and its output, as plain text for copying
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct HistoryEntry(pub String);
+
+/// The run of history entries between where a selection started and where it ended up
+#[derive(Resource, Debug, Default)]
+pub struct HistorySelection {
+    anchor: Option<Entity>,
+    end: Option<Entity>,
+    /// Text of the selected entries, ready to copy
+    pub text: String,
+}
+
+impl HistorySelection {
+    pub fn clear(&mut self) {
+        *self = default();
+    }
+}
+
+fn start_selection(
+    trigger: Trigger<Pointer<Pressed>>,
+    entry_query: Query<(), With<HistoryEntry>>,
+    mut selection: ResMut<HistorySelection>,
+) {
+    let entry = trigger.target();
+    if trigger.event().button != PointerButton::Primary || !entry_query.contains(entry) {
+        return;
+    }
+
+    // Clicking the only selected entry again deselects it
+    if selection.anchor == Some(entry) && selection.end == Some(entry) {
+        selection.clear();
+    } else {
+        selection.anchor = Some(entry);
+        selection.end = Some(entry);
+    }
+}
+
+fn extend_selection(
+    trigger: Trigger<Pointer<DragEnter>>,
+    entry_query: Query<(), With<HistoryEntry>>,
+    mut selection: ResMut<HistorySelection>,
+) {
+    let entry = trigger.target();
+    if trigger.event().button == PointerButton::Primary
+        && selection.anchor.is_some()
+        && entry_query.contains(entry)
+    {
+        selection.end = Some(entry);
+    }
+}
+
+fn highlight_selection(
+    mut selection: ResMut<HistorySelection>,
+    history_query: Query<&Children, With<TerminalHistory>>,
+    mut entry_query: Query<(&HistoryEntry, &mut BackgroundColor)>,
+) {
+    let mut selected_text = Vec::new();
+    for children in &history_query {
+        let position = |entity: Option<Entity>| {
+            children
+                .into_iter()
+                .position(|&child| Some(child) == entity)
+        };
+        // Entries that were cleared away take the selection with them
+        let selected = position(selection.anchor)
+            .zip(position(selection.end))
+            .map(|(anchor, end)| anchor.min(end)..=anchor.max(end));
+
+        for (index, &child) in children.into_iter().enumerate() {
+            let Ok((entry, mut background)) = entry_query.get_mut(child) else {
+                continue;
+            };
+            if selected
+                .as_ref()
+                .is_some_and(|selected| selected.contains(&index))
+            {
+                background.set_if_neq(BackgroundColor(SELECTION_BACKGROUND));
+                selected_text.push(entry.0.clone());
+            } else {
+                background.set_if_neq(BackgroundColor(Color::NONE));
+            }
+        }
+    }
+
+    let selected_text = selected_text.join("\n");
+    if selection.text != selected_text {
+        selection.text = selected_text;
+    }
+}
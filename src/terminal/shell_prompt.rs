@@ -0,0 +1,95 @@
+//! The prompt shown before the line being typed, PS1-style: a format with tokens for who and
+//! where the player is, filled in as that changes (`\u@\h:~\$` shows `guest@r01:~$`).
+
+use bevy::prelude::*;
+
+use crate::screens::Screen;
+
+const DEFAULT_USER: &str = "guest";
+const DEFAULT_HOST: &str = "localhost";
+
+/// What the prompt says and what goes into it
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct TerminalPrompt {
+    /// `\u` is the user, `\h` the host, `\a` the alert level, `\$` is `#` for root and `$` for
+    /// anyone else, and `\\` a backslash. A space always follows.
+    pub format: String,
+    pub user: String,
+    /// The node the player's on
+    pub host: String,
+    /// How alarmed the network is
+    pub alert: u8,
+}
+
+impl Default for TerminalPrompt {
+    fn default() -> Self {
+        Self {
+            format: ">".to_string(),
+            user: DEFAULT_USER.to_string(),
+            host: DEFAULT_HOST.to_string(),
+            alert: 0,
+        }
+    }
+}
+
+impl TerminalPrompt {
+    /// The prompt as it shows
+    pub fn render(&self) -> String {
+        let mut prompt = String::new();
+        let mut chars = self.format.chars();
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                prompt.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('u') => prompt.push_str(&self.user),
+                Some('h') => prompt.push_str(&self.host),
+                Some('a') => prompt.push_str(&self.alert.to_string()),
+                Some('$') => prompt.push(if self.user == "root" { '#' } else { '$' }),
+                // Unknown tokens (and a `\` on the end) show as typed
+                Some(other) if other != '\\' => {
+                    prompt.push('\\');
+                    prompt.push(other);
+                }
+                _ => prompt.push('\\'),
+            }
+        }
+        prompt.push(' ');
+        prompt
+    }
+}
+
+// A new run starts back on the player's own machine (keeping their prompt format)
+fn reset_session(mut prompt: ResMut<TerminalPrompt>) {
+    prompt.user = DEFAULT_USER.to_string();
+    prompt.host = DEFAULT_HOST.to_string();
+    prompt.alert = 0;
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<TerminalPrompt>();
+    app.init_resource::<TerminalPrompt>();
+    app.add_systems(OnEnter(Screen::Gameplay), reset_session);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let mut prompt = TerminalPrompt::default();
+        assert_eq!(prompt.render(), "> ");
+
+        prompt.format = "\\u@\\h:~\\$".to_string();
+        prompt.host = "r01".to_string();
+        assert_eq!(prompt.render(), "guest@r01:~$ ");
+        prompt.user = "root".to_string();
+        assert_eq!(prompt.render(), "root@r01:~# ");
+
+        prompt.format = "[\\a] \\x\\\\".to_string();
+        assert_eq!(prompt.render(), "[0] \\x\\ ");
+    }
+}
@@ -0,0 +1,120 @@
+//! Retro "speech" for terminal output: a short procedural blip per character, pitched by
+//! the character (Animalese-style), so no audio has to be recorded per line.
+
+use std::time::Duration;
+
+use bevy::{
+    audio::{AddAudioSource, Decodable, Source},
+    prelude::*,
+};
+
+use crate::audio::SoundEffect;
+
+const SAMPLE_RATE: u32 = 44_100;
+const BLIP_SECS: f32 = 0.045;
+const BASE_PITCH: f32 = 220.0;
+const BLIP_VOLUME: f32 = 0.15;
+// Long outputs would babble forever
+const MAX_BLIPS: usize = 48;
+
+/// Whether terminal output is "spoken".
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct TerminalSpeech(pub bool);
+
+/// A procedurally generated line of babble.
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct Babble {
+    // Pitch of each blip in Hz
+    pitches: Vec<f32>,
+}
+
+impl Babble {
+    pub fn new(text: &str) -> Self {
+        Self {
+            pitches: text
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .take(MAX_BLIPS)
+                .map(pitch_for)
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pitches.is_empty()
+    }
+}
+
+// Same character, same pitch: map it onto two octaves of semitones above the base pitch
+fn pitch_for(c: char) -> f32 {
+    let semitone = (c.to_ascii_lowercase() as u32 % 24) as f32;
+    BASE_PITCH * 2.0_f32.powf(semitone / 12.0)
+}
+
+pub struct BabbleDecoder {
+    pitches: Vec<f32>,
+    sample: usize,
+    samples_per_blip: usize,
+}
+
+impl Iterator for BabbleDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let blip = self.sample / self.samples_per_blip;
+        let pitch = *self.pitches.get(blip)?;
+
+        // Square wave with a linear decay, so blips don't click into each other
+        let t = self.sample as f32 / SAMPLE_RATE as f32;
+        let progress = (self.sample % self.samples_per_blip) as f32 / self.samples_per_blip as f32;
+        let square = if (t * pitch).fract() < 0.5 { 1.0 } else { -1.0 };
+        self.sample += 1;
+
+        Some(square * BLIP_VOLUME * (1.0 - progress))
+    }
+}
+
+impl Source for BabbleDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        Some(Duration::from_secs_f32(
+            BLIP_SECS * self.pitches.len() as f32,
+        ))
+    }
+}
+
+impl Decodable for Babble {
+    type DecoderItem = <BabbleDecoder as Iterator>::Item;
+    type Decoder = BabbleDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        BabbleDecoder {
+            pitches: self.pitches.clone(),
+            sample: 0,
+            samples_per_blip: (BLIP_SECS * SAMPLE_RATE as f32) as usize,
+        }
+    }
+}
+
+/// A babble audio instance, played as a sound effect.
+pub fn babble(handle: Handle<Babble>) -> impl Bundle {
+    (AudioPlayer(handle), PlaybackSettings::DESPAWN, SoundEffect)
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<TerminalSpeech>();
+    app.init_resource::<TerminalSpeech>();
+    app.add_audio_source::<Babble>();
+}
@@ -0,0 +1,40 @@
+//! Optional timestamps on terminal history, for working out what happened when.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::screens::Screen;
+
+/// #808080
+pub const TIMESTAMP_TEXT: Color = Color::srgb(0.5, 0.5, 0.5);
+
+/// Whether terminal history lines are prefixed with the time they were produced.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct TerminalTimestamps(pub bool);
+
+/// When the current run started, in (pausable) game time
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct RunClock(Duration);
+
+impl RunClock {
+    /// `[mm:ss]` since the run started
+    pub fn stamp(&self, time: &Time) -> String {
+        let secs = time.elapsed().saturating_sub(self.0).as_secs();
+        format!("[{:02}:{:02}] ", secs / 60, secs % 60)
+    }
+}
+
+fn start_run_clock(mut clock: ResMut<RunClock>, time: Res<Time>) {
+    clock.0 = time.elapsed();
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<TerminalTimestamps>();
+    app.register_type::<RunClock>();
+    app.init_resource::<TerminalTimestamps>();
+    app.init_resource::<RunClock>();
+    app.add_systems(OnEnter(Screen::Gameplay), start_run_clock);
+}
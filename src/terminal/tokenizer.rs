@@ -0,0 +1,207 @@
+//! Splits command lines into words, shell-style: on whitespace, except inside `"double"` or
+//! `'single'` quotes. Outside single quotes, `\` escapes whitespace, quotes, `\`, `;` and `&`,
+//! and `\n` and `\t` are a line break and a tab; before anything else it's kept as typed, so
+//! `set prompt \u@\h` still works. Both running commands and tab completing them go through
+//! here, so they always agree on the words.
+
+use std::ops::Range;
+
+/// A word of a command line
+#[derive(Debug, PartialEq)]
+pub struct Token {
+    /// Where it is in the line, quotes and all
+    pub range: Range<usize>,
+    /// What it says, with its quotes and escapes taken out
+    pub text: String,
+    /// Whether any of it was quoted or escaped, so wildcards in it are taken literally
+    pub quoted: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    // Outside quotes and not escaped, so whitespace and separators mean something
+    Bare,
+    // Part of a word, whatever it is, because it's quoted or escaped
+    Quoted,
+    // A quote or `\` that only says how the characters around it are read
+    Syntax,
+}
+
+fn is_escapable(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '"' | '\'' | '\\' | ';' | '&')
+}
+
+// What the character after a `\` stands for, if the `\` escapes it
+fn unescape(c: char) -> Option<char> {
+    match c {
+        'n' => Some('\n'),
+        't' => Some('\t'),
+        c if is_escapable(c) => Some(c),
+        _ => None,
+    }
+}
+
+// Every character of the line, with its byte offset and how it's read
+fn scan(line: &str) -> impl Iterator<Item = (usize, char, Kind)> + '_ {
+    let mut quote = None;
+    // The character after a `\`, once the `\` itself has been seen
+    let mut escaped = None;
+    let mut chars = line.char_indices().peekable();
+    std::iter::from_fn(move || {
+        if let Some(escaped) = escaped.take() {
+            return Some(escaped);
+        }
+        let (index, c) = chars.next()?;
+        let kind = match (quote, c) {
+            (Some(open), c) if c == open => {
+                quote = None;
+                Kind::Syntax
+            }
+            (Some('\''), _) => Kind::Quoted,
+            (_, '\\')
+                if chars
+                    .peek()
+                    .is_some_and(|&(_, next)| unescape(next).is_some()) =>
+            {
+                escaped = chars.next().and_then(|(next_index, next)| {
+                    Some((next_index, unescape(next)?, Kind::Quoted))
+                });
+                Kind::Syntax
+            }
+            (Some(_), _) => Kind::Quoted,
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                Kind::Syntax
+            }
+            (None, _) => Kind::Bare,
+        };
+        Some((index, c, kind))
+    })
+}
+
+/// Splits a line into its words. An unclosed quote runs to the end of the line (it's probably
+/// still being typed).
+pub fn tokenize(line: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut current: Option<Token> = None;
+    for (index, c, kind) in scan(line) {
+        if kind == Kind::Bare && c.is_whitespace() {
+            tokens.extend(current.take());
+            continue;
+        }
+        let token = current.get_or_insert_with(|| Token {
+            range: index..index,
+            text: String::new(),
+            quoted: false,
+        });
+        token.range.end = index + c.len_utf8();
+        token.quoted |= kind != Kind::Bare;
+        if kind != Kind::Syntax {
+            token.text.push(c);
+        }
+    }
+    tokens.extend(current);
+    tokens
+}
+
+/// Byte offsets and characters of the line that aren't quoted or escaped, where `;` and `&&`
+/// separate commands
+pub fn bare_chars(line: &str) -> impl Iterator<Item = (usize, char)> + '_ {
+    scan(line)
+        .filter(|&(_, _, kind)| kind == Kind::Bare)
+        .map(|(index, c, _)| (index, c))
+}
+
+/// Escapes a word so it comes back out of `tokenize` as itself
+pub fn escape(word: &str) -> String {
+    if word.is_empty() {
+        return "\"\"".to_string();
+    }
+    let mut escaped = String::with_capacity(word.len());
+    for c in word.chars() {
+        match c {
+            '\n' => escaped.push_str(r"\n"),
+            '\t' => escaped.push_str(r"\t"),
+            c if is_escapable(c) => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Puts words back together into a line that tokenizes into the same words
+pub fn join(words: &[String]) -> String {
+    words
+        .iter()
+        .map(|word| escape(word))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(line: &str) -> Vec<String> {
+        tokenize(line).into_iter().map(|token| token.text).collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(texts("  ls  r01 "), vec!["ls", "r01"]);
+        assert_eq!(
+            texts(r#"cat "readme file.txt""#),
+            vec!["cat", "readme file.txt"]
+        );
+        assert_eq!(
+            texts(r#"note 'it said "hi"' ok"#),
+            vec!["note", r#"it said "hi""#, "ok"]
+        );
+        assert_eq!(texts(r"note a\ b\;c"), vec!["note", "a b;c"]);
+        assert_eq!(texts(r#"note "a\"b" '\'"#), vec!["note", r#"a"b"#, r"\"]);
+        // Backslashes that don't escape anything are kept
+        assert_eq!(
+            texts(r"set prompt \u@\h\$"),
+            vec!["set", "prompt", r"\u@\h\$"]
+        );
+        // ...but line breaks and tabs can be typed, except in single quotes
+        assert_eq!(
+            texts(r#"echo a\nb "\t\\" '\n'"#),
+            vec!["echo", "a\nb", "\t\\", r"\n"]
+        );
+        // Quotes join onto the words around them, and empty quotes are still a word
+        assert_eq!(texts(r#"a"b c"d """#), vec!["ab cd", ""]);
+        // Still typing
+        assert_eq!(texts(r#"ls "ws"#), vec!["ls", "ws"]);
+
+        let tokens = tokenize(r#"ls "ws 01" \"x"#);
+        let ranges = tokens
+            .iter()
+            .map(|token| token.range.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, vec![0..2, 3..10, 11..14]);
+        let quoted = tokens.iter().map(|token| token.quoted).collect::<Vec<_>>();
+        assert_eq!(quoted, vec![false, true, true]);
+    }
+
+    #[test]
+    fn test_join_round_trips() {
+        let words = [
+            "note",
+            "a b",
+            r#"it's "x"; y && z"#,
+            r"\u",
+            "",
+            "tab\tline\n",
+        ]
+        .map(String::from)
+        .to_vec();
+        let joined = join(&words);
+        assert_eq!(texts(&joined), words);
+        // Nothing in the words can split the line into several commands
+        assert!(!bare_chars(&joined).any(|(_, c)| c == ';' || c == '&'));
+    }
+}
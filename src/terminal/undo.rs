@@ -0,0 +1,55 @@
+//! Undo for the player's own bookkeeping (notes, macros). What happens to the network stays
+//! happened.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::screens::Screen;
+
+// Oldest entries fall off the bottom past this
+const UNDO_LIMIT: usize = 32;
+
+/// A housekeeping change that can be reverted.
+#[derive(Debug, Reflect)]
+pub enum UndoAction {
+    NoteAdded,
+    NoteDeleted {
+        index: usize,
+        text: String,
+    },
+    MacroSaved {
+        name: String,
+        // What the macro was before it got overwritten, if it existed
+        previous: Option<Vec<String>>,
+    },
+}
+
+/// Recent undoable changes, newest last.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct UndoHistory(VecDeque<UndoAction>);
+
+impl UndoHistory {
+    pub fn push(&mut self, action: UndoAction) {
+        if self.0.len() == UNDO_LIMIT {
+            self.0.pop_front();
+        }
+        self.0.push_back(action);
+    }
+
+    pub fn pop(&mut self) -> Option<UndoAction> {
+        self.0.pop_back()
+    }
+}
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<UndoHistory>();
+    app.init_resource::<UndoHistory>();
+    app.add_systems(OnEnter(Screen::Gameplay), clear_undo_history);
+}
+
+// Notes start over every run, so there's nothing from the last one to undo
+fn clear_undo_history(mut undo: ResMut<UndoHistory>) {
+    undo.0.clear();
+}
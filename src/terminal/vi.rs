@@ -0,0 +1,132 @@
+//! An optional vi-style way of editing the terminal line. Esc drops into normal mode, where
+//! keys move around and change the line instead of typing, and `i`/`a` go back to typing.
+
+use bevy::prelude::*;
+
+use super::{TerminalCursor, command};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<TerminalEditMode>();
+    app.init_resource::<TerminalEditMode>();
+}
+
+/// How the terminal line is edited
+#[derive(Resource, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource)]
+pub enum TerminalEditMode {
+    /// Readline's usual keys, always typing
+    #[default]
+    Emacs,
+    /// Typing until Esc, then vi motions until `i` or `a`
+    Vi,
+}
+
+impl TerminalEditMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            TerminalEditMode::Emacs => "emacs",
+            TerminalEditMode::Vi => "vi",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "emacs" => Some(TerminalEditMode::Emacs),
+            "vi" => Some(TerminalEditMode::Vi),
+            _ => None,
+        }
+    }
+}
+
+/// Where vi editing is at on the current line
+#[derive(Debug, Default)]
+pub(super) struct ViState {
+    /// In normal mode (motions) rather than insert mode (typing)
+    pub normal: bool,
+    // An operator (`d`) waiting for its motion
+    pending: Option<char>,
+}
+
+impl TerminalCursor {
+    /// Esc: stop typing, stepping back onto the last character typed like vi does
+    pub(super) fn vi_normal_mode(&mut self) {
+        if !self.vi.normal {
+            self.vi.normal = true;
+            self.move_left();
+        }
+        self.vi.pending = None;
+    }
+
+    /// Whether Esc means something to vi right now (leaving insert mode, or dropping an
+    /// operator), rather than leaving the terminal
+    pub(super) fn vi_uses_escape(&self) -> bool {
+        !self.vi.normal || self.vi.pending.is_some()
+    }
+
+    // `w` goes to the start of the next word, where Ctrl+Right goes to the end of this one
+    fn vi_next_word(&mut self) {
+        self.cursor_location = command::words(&self.current_input)
+            .map(|word| word.start)
+            .find(|&start| start > self.cursor_location)
+            .unwrap_or(self.current_input.len());
+    }
+
+    /// A key pressed in normal mode
+    pub(super) fn vi_key(&mut self, key: char) {
+        if self.vi.pending.take() == Some('d') {
+            // `dd` deletes the whole line. Other motions after `d` aren't supported (yet).
+            if key == 'd' {
+                self.move_home();
+                self.kill_to_end();
+            }
+            return;
+        }
+
+        match key {
+            'h' => self.move_left(),
+            'l' => self.move_right(),
+            'w' => self.vi_next_word(),
+            'b' => self.move_word_left(),
+            '0' => self.move_home(),
+            '$' => self.move_end(),
+            'x' => self.delete_forward(),
+            'd' => self.vi.pending = Some('d'),
+            'i' => self.vi.normal = false,
+            'a' => {
+                self.move_right();
+                self.vi.normal = false;
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vi_keys() {
+        let mut cursor = TerminalCursor::default();
+        cursor.insert("ls r01 ws01");
+        cursor.vi_normal_mode();
+        assert!(cursor.vi.normal);
+        assert_eq!(cursor.cursor_location, 10);
+
+        // Typing in normal mode moves around and edits instead
+        for key in "bbxx".chars() {
+            cursor.vi_key(key);
+        }
+        assert_eq!(cursor.current_input, "ls 1 ws01");
+
+        cursor.vi_key('a');
+        assert!(!cursor.vi.normal);
+        cursor.insert("0");
+        assert_eq!(cursor.current_input, "ls 10 ws01");
+
+        cursor.vi_normal_mode();
+        cursor.vi_key('d');
+        cursor.vi_key('d');
+        assert_eq!(cursor.current_input, "");
+    }
+}
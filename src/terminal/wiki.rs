@@ -0,0 +1,158 @@
+//! The in-game encyclopedia. Its entries are written in `assets/text/wiki.strings` (`topic =
+//! text`, like `terminal.strings`, and hot reloaded the same way), and each one unlocks the first
+//! time the player runs into what it's about. So far that's the kinds of machine on a network,
+//! which unlock when `ls` or `auto` first shows one. `wiki` reads them in the terminal, and the
+//! pause menu has a page of everything unlocked.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use super::responses::TerminalStrings;
+
+const WIKI_STRINGS_PATH: &str = "text/wiki.strings";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Wiki>();
+    app.add_systems(Startup, load_wiki);
+    app.add_systems(Update, update_wiki);
+}
+
+/// The encyclopedia, and which of its entries the player has unlocked
+#[derive(Resource, Debug, Default)]
+pub struct Wiki {
+    handle: Handle<TerminalStrings>,
+    entries: HashMap<String, String>,
+    // In the order they were unlocked
+    unlocked: Vec<String>,
+}
+
+impl Wiki {
+    /// Unlocks a topic's entry, returning whether it wasn't already. Topics nobody has written
+    /// an entry for stay locked.
+    pub fn unlock(&mut self, topic: &str) -> bool {
+        if !self.entries.contains_key(topic) || self.is_unlocked(topic) {
+            return false;
+        }
+        self.unlocked.push(topic.to_string());
+        true
+    }
+
+    pub fn is_unlocked(&self, topic: &str) -> bool {
+        self.unlocked.iter().any(|unlocked| unlocked == topic)
+    }
+
+    /// Whether there's an entry for the topic at all, unlocked or not
+    pub fn has_entry(&self, topic: &str) -> bool {
+        self.entries.contains_key(topic)
+    }
+
+    /// A topic's entry, once it's been unlocked
+    pub fn entry(&self, topic: &str) -> Option<&str> {
+        if !self.is_unlocked(topic) {
+            return None;
+        }
+        self.entries.get(topic).map(String::as_str)
+    }
+
+    /// Unlocked topics and their entries, in the order they were unlocked
+    pub fn unlocked(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.unlocked
+            .iter()
+            .filter_map(|topic| Some((topic.as_str(), self.entries.get(topic)?.as_str())))
+    }
+}
+
+fn load_wiki(mut wiki: ResMut<Wiki>, asset_server: Res<AssetServer>) {
+    wiki.handle = asset_server.load(WIKI_STRINGS_PATH);
+}
+
+// Picks up the file when it loads, and again every time it's saved
+fn update_wiki(
+    mut events: EventReader<AssetEvent<TerminalStrings>>,
+    mut wiki: ResMut<Wiki>,
+    strings: Res<Assets<TerminalStrings>>,
+) {
+    for event in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = event
+        else {
+            continue;
+        };
+        if *id != wiki.handle.id() {
+            continue;
+        }
+        if let Some(loaded) = strings.get(*id) {
+            wiki.entries = loaded.0.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::NetworkGraph,
+        terminal::command::{ScratchResources, run_line},
+    };
+
+    #[test]
+    fn test_unlocking_entries() {
+        let strings = TerminalStrings::parse(
+            "router = Passes traffic between networks.\nswitch = Joins machines up.\n",
+        )
+        .unwrap();
+        let mut wiki = Wiki {
+            entries: strings.0,
+            ..default()
+        };
+        assert_eq!(wiki.entry("switch"), None);
+        assert!(wiki.has_entry("switch"));
+
+        assert!(wiki.unlock("switch"));
+        assert!(wiki.unlock("router"));
+        // Only the first time counts, and there's nothing to unlock without an entry
+        assert!(!wiki.unlock("switch"));
+        assert!(!wiki.unlock("honeypot"));
+
+        assert_eq!(wiki.entry("switch"), Some("Joins machines up."));
+        assert_eq!(
+            wiki.unlocked().map(|(topic, _)| topic).collect::<Vec<_>>(),
+            vec!["switch", "router"]
+        );
+    }
+
+    #[test]
+    fn test_ls_unlocks_what_it_shows() {
+        let graph =
+            NetworkGraph::parse("type router r01\ntype pc ws01\ntype server s01\nlink r01 ws01")
+                .unwrap();
+        let mut resources = ScratchResources {
+            wiki: Wiki {
+                entries: TerminalStrings::parse("router = Routes.\npc = A desk.\nserver = Serves.")
+                    .unwrap()
+                    .0,
+                ..default()
+            },
+            ..default()
+        };
+        let mut context = resources.context(Some(&graph));
+
+        let output = run_line("wiki router", &mut context).unwrap();
+        assert_eq!(output.lines, vec!["The router entry is still locked."]);
+
+        // Only ws01 hangs off r01, so that's the only new thing it shows
+        let output = run_line("ls r01", &mut context).unwrap();
+        assert_eq!(
+            output.lines,
+            vec!["ws01 (pc)", "New wiki entry: pc (\"wiki pc\" to read it)"]
+        );
+        let output = run_line("ls", &mut context).unwrap();
+        assert_eq!(output.lines.len(), 5);
+
+        let output = run_line("wiki", &mut context).unwrap();
+        assert_eq!(output.lines, vec!["pc router server"]);
+        let output = run_line("wiki pc", &mut context).unwrap();
+        assert_eq!(output.lines, vec!["pc: A desk."]);
+        assert!(!run_line("wiki honeypot", &mut context).unwrap().success);
+    }
+}
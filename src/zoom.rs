@@ -0,0 +1,35 @@
+//! Low-vision zoom: scales the whole UI (terminal text, HUD, panels), independently of
+//! the OS scale factor.
+
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, zoom_hotkeys);
+}
+
+const MIN_ZOOM: f32 = 0.75;
+const MAX_ZOOM: f32 = 2.0;
+pub const ZOOM_STEP: f32 = 0.25;
+
+/// Zooms the UI in (or out, if `step` is negative), staying within the supported range.
+pub fn zoom(ui_scale: &mut UiScale, step: f32) {
+    ui_scale.0 = (ui_scale.0 + step).clamp(MIN_ZOOM, MAX_ZOOM);
+}
+
+// Ctrl+= zooms in, Ctrl+- zooms out
+fn zoom_hotkeys(input: Res<ButtonInput<KeyCode>>, mut ui_scale: ResMut<UiScale>) {
+    if !input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        return;
+    }
+    // Ctrl+Alt is AltGr on Windows, which types `}` on = in French layouts
+    if input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]) {
+        return;
+    }
+
+    if input.any_just_pressed([KeyCode::Equal, KeyCode::NumpadAdd]) {
+        zoom(&mut ui_scale, ZOOM_STEP);
+    }
+    if input.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+        zoom(&mut ui_scale, -ZOOM_STEP);
+    }
+}